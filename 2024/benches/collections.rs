@@ -0,0 +1,111 @@
+// Run days 5 and 6 with SipHash collections and with the ones they ship with
+//
+// Day 5 keeps its rules in an `OrderRules` hashed with SipHash or FxHash, or
+// in the `PrecedenceMatrix` bit matrix it normally uses. Day 6 records the
+// guard's path in a SipHash or FxHash set. Each part runs on the example,
+// and on the real input too once `data/inputs/NN.txt` has been downloaded.
+//
+//   cargo bench -p aoc-2024 --bench collections
+
+use std::hash::RandomState;
+use std::hint::black_box;
+
+use aoc_common::collections::FxBuildHasher;
+use aoc_common::graph::{OrderRules, PrecedenceMatrix};
+use aoc_common::input::{self, Source};
+use criterion::{Criterion, criterion_group, criterion_main};
+
+// The days are binaries, so borrow their sources; their `main` and tests go unused
+#[allow(dead_code, unused_imports)]
+#[path = "../src/bin/day05.rs"]
+mod day05;
+#[allow(dead_code, unused_imports)]
+#[path = "../src/bin/day06.rs"]
+mod day06;
+
+type Solve = fn(&str) -> usize;
+
+/// The example, plus the real input if it has been downloaded
+fn inputs(day: &str, example: &str) -> Vec<(&'static str, String)> {
+    let mut inputs = vec![("example", example.to_string())];
+    if let Ok(input) = input::read(env!("CARGO_MANIFEST_DIR"), day, &Source::Default) {
+        inputs.push(("input", input));
+    }
+    inputs
+}
+
+/// Time each way of solving one part, in one group per input
+fn compare(c: &mut Criterion, day: &str, example: &str, part: &str, solvers: &[(&str, Solve)]) {
+    for (name, input) in inputs(day, example) {
+        let mut group = c.benchmark_group(format!("day{}/{}/{}", day, part, name));
+        for &(collection, solve) in solvers {
+            group.bench_function(collection, |b| b.iter(|| solve(black_box(&input))));
+        }
+        group.finish();
+    }
+}
+
+fn bench_day05(c: &mut Criterion) {
+    let example = include_str!("../data/examples/05.txt");
+    compare(
+        c,
+        "05",
+        example,
+        "part1",
+        &[
+            (
+                "HashSet",
+                day05::part1_with::<OrderRules<usize, RandomState>>,
+            ),
+            (
+                "FxHashSet",
+                day05::part1_with::<OrderRules<usize, FxBuildHasher>>,
+            ),
+            ("PrecedenceMatrix", day05::part1_with::<PrecedenceMatrix>),
+        ],
+    );
+    compare(
+        c,
+        "05",
+        example,
+        "part2",
+        &[
+            (
+                "HashSet",
+                day05::part2_with::<OrderRules<usize, RandomState>>,
+            ),
+            (
+                "FxHashSet",
+                day05::part2_with::<OrderRules<usize, FxBuildHasher>>,
+            ),
+            ("PrecedenceMatrix", day05::part2_with::<PrecedenceMatrix>),
+        ],
+    );
+}
+
+fn bench_day06(c: &mut Criterion) {
+    let example = include_str!("../data/examples/06.txt");
+    compare(
+        c,
+        "06",
+        example,
+        "part1",
+        &[
+            ("HashSet", day06::part1_with::<RandomState>),
+            ("FxHashSet", day06::part1_with::<FxBuildHasher>),
+        ],
+    );
+    compare(
+        c,
+        "06",
+        example,
+        "part2",
+        &[
+            ("HashSet", day06::part2_with::<RandomState>),
+            ("FxHashSet", day06::part2_with::<FxBuildHasher>),
+        ],
+    );
+}
+
+criterion_group!(benches, bench_day05, bench_day06);
+criterion_main!(benches);
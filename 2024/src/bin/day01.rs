@@ -1,20 +1,6 @@
-use std::{collections::HashMap, str, time::Instant};
+use std::{collections::HashMap, str};
 
-fn main() {
-    let input = include_str!("../../data/inputs/01.txt");
-
-    let start = Instant::now();
-    let part1_result = part1(input);
-    let part1_time = start.elapsed();
-    println!("Part 1: {} ({:?})", part1_result, part1_time);
-
-    let start = Instant::now();
-    let part2_result = part2(input);
-    let part2_time = start.elapsed();
-    println!("Part 2: {} ({:?})", part2_result, part2_time);
-
-    println!("\nTotal time: {:?}", part1_time + part2_time);
-}
+aoc_common::aoc_main!("01");
 
 fn format_input(input: &str) -> Vec<(usize, usize)> {
     input
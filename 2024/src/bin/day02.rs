@@ -1,20 +1,4 @@
-use std::time::Instant;
-
-fn main() {
-    let input = include_str!("../../data/inputs/02.txt");
-
-    let start = Instant::now();
-    let part1_result = part1(input);
-    let part1_time = start.elapsed();
-    println!("Part 1: {} ({:?})", part1_result, part1_time);
-
-    let start = Instant::now();
-    let part2_result = part2(input);
-    let part2_time = start.elapsed();
-    println!("Part 2: {} ({:?})", part2_result, part2_time);
-
-    println!("\nTotal time: {:?}", part1_time + part2_time);
-}
+aoc_common::aoc_main!("02");
 
 fn format_input(input: &str) -> Vec<Vec<usize>> {
     input
@@ -38,7 +22,7 @@ fn is_safe_report(report: &[usize]) -> bool {
     let valid_diffs = report.windows(2).all(|w| match w {
         [current, next] => {
             let diff = current.abs_diff(*next);
-            diff >= 1 && diff <= 3
+            (1..=3).contains(&diff)
         }
         _ => unreachable!(),
     });
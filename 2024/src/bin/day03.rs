@@ -1,59 +1,61 @@
-use std::time::Instant;
+use aoc_common::scan::{Parser, alt, delimited, find_all, literal, uint};
+use aoc_common::vm::{self, Control, Cpu, Machine};
 
-use regex::Regex;
+aoc_common::aoc_main!("03");
 
-fn main() {
-    let input = include_str!("../../data/inputs/03.txt");
+#[derive(Debug, Clone)]
+enum Instruction {
+    Mul(usize, usize),
+    Do,
+    Dont,
+}
 
-    let start = Instant::now();
-    let part1_result = part1(input);
-    let part1_time = start.elapsed();
-    println!("Part 1: {} ({:?})", part1_result, part1_time);
+const DISABLED: usize = 0;
+const SUM: usize = 1;
 
-    let start = Instant::now();
-    let part2_result = part2(input);
-    let part2_time = start.elapsed();
-    println!("Part 2: {} ({:?})", part2_result, part2_time);
+impl vm::Instruction for Instruction {
+    type Value = usize;
 
-    println!("\nTotal time: {:?}", part1_time + part2_time);
+    fn execute(&self, cpu: &mut Cpu<usize>) -> Control {
+        match *self {
+            Instruction::Mul(x, y) if cpu.registers[DISABLED] == 0 => cpu.registers[SUM] += x * y,
+            Instruction::Mul(..) => {}
+            Instruction::Do => cpu.registers[DISABLED] = 0,
+            Instruction::Dont => cpu.registers[DISABLED] = 1,
+        }
+        Control::Next
+    }
 }
 
-fn part1(input: &str) -> usize {
-    let re = Regex::new(r"mul\((\d+),(\d+)\)").unwrap();
-
-    re.captures_iter(input)
-        .map(|cap| {
-            let x: usize = cap[1].parse().unwrap();
-            let y: usize = cap[2].parse().unwrap();
-            x * y
-        })
-        .sum()
+/// `mul(X,Y)`, `do()` or `don't()`
+fn instruction<'a>() -> impl Parser<'a, Instruction> {
+    let mul = delimited(
+        literal("mul("),
+        uint().skip(literal(",")).then(uint()),
+        literal(")"),
+    );
+    alt((
+        mul.map(|(x, y)| Instruction::Mul(x, y)),
+        literal("do()").map(|_| Instruction::Do),
+        literal("don't()").map(|_| Instruction::Dont),
+    ))
 }
 
-fn part2(input: &str) -> usize {
-    let re = Regex::new(r"mul\((?P<x>\d+),(?P<y>\d+)\)|(?P<op>don\'t\(\)|do\(\))").unwrap();
-    let mut is_enabled = true;
-    let mut sum = 0;
-
-    for cap in re.captures_iter(input) {
-        if let Some(op) = cap.name("op") {
-            match op.as_str() {
-                "don't()" => is_enabled = false,
-                "do()" => is_enabled = true,
-                _ => panic!("Not implemented: {:?}", op),
-            }
-        } else if let (Some(x), Some(y)) = (cap.name("x"), cap.name("y")) {
-            if !is_enabled {
-                continue;
-            }
+/// Run the instructions until they run out, returning the sum register
+fn run(program: Vec<Instruction>) -> usize {
+    let mut machine = Machine::new(program, 2);
+    machine.run();
+    machine.registers()[SUM]
+}
 
-            let x: usize = x.as_str().parse().unwrap();
-            let y: usize = y.as_str().parse().unwrap();
-            sum += x * y;
-        }
-    }
+fn part1(input: &str) -> usize {
+    run(find_all(instruction(), input)
+        .filter(|instruction| matches!(instruction, Instruction::Mul(..)))
+        .collect())
+}
 
-    sum
+fn part2(input: &str) -> usize {
+    run(find_all(instruction(), input).collect())
 }
 
 #[cfg(test)]
@@ -1,96 +1,89 @@
-use std::{cmp::Ordering, collections::HashSet, time::Instant};
+use std::hash::BuildHasher;
 
-fn main() {
-    let input = include_str!("../../data/inputs/05.txt");
+use aoc_common::graph::{CycleError, OrderRules, PrecedenceMatrix};
+use aoc_common::parse;
 
-    let start = Instant::now();
-    let part1_result = part1(input);
-    let part1_time = start.elapsed();
-    println!("Part 1: {} ({:?})", part1_result, part1_time);
+aoc_common::aoc_main!("05");
 
-    let start = Instant::now();
-    let part2_result = part2(input);
-    let part2_time = start.elapsed();
-    println!("Part 2: {} ({:?})", part2_result, part2_time);
+type Pages = Vec<Vec<usize>>;
 
-    println!("\nTotal time: {:?}", part1_time + part2_time);
+/// What the parts need from the rules, so the collections bench can run them
+/// on `OrderRules` as well as the `PrecedenceMatrix` they normally use
+pub trait Rules: FromIterator<(usize, usize)> {
+    fn is_consistent_order(&self, order: &[usize]) -> bool;
+    fn toposort(&self, nodes: &[usize]) -> Result<Vec<usize>, CycleError<usize>>;
 }
 
-type Rules = HashSet<(usize, usize)>;
-type Pages = Vec<Vec<usize>>;
+impl Rules for PrecedenceMatrix {
+    fn is_consistent_order(&self, order: &[usize]) -> bool {
+        PrecedenceMatrix::is_consistent_order(self, order)
+    }
 
-fn format_input(input: &str) -> (Rules, Pages) {
-    let (left, right) = input.split_once("\n\n").unwrap();
+    fn toposort(&self, nodes: &[usize]) -> Result<Vec<usize>, CycleError<usize>> {
+        PrecedenceMatrix::toposort(self, nodes)
+    }
+}
 
-    let rules: Rules = left
-        .lines()
-        .map(|line| {
-            let (first, second) = line.split_once('|').unwrap();
-            (
-                first.parse::<usize>().unwrap(),
-                second.parse::<usize>().unwrap(),
-            )
-        })
-        .collect();
+impl<S: BuildHasher + Default> Rules for OrderRules<usize, S> {
+    fn is_consistent_order(&self, order: &[usize]) -> bool {
+        OrderRules::is_consistent_order(self, order)
+    }
 
-    let pages: Pages = right
+    fn toposort(&self, nodes: &[usize]) -> Result<Vec<usize>, CycleError<usize>> {
+        OrderRules::toposort(self, nodes)
+    }
+}
+
+fn format_input<R: Rules>(input: &str) -> (R, Pages) {
+    let mut blocks = parse::split_blocks(input);
+    let (Some(rules), Some(pages)) = (blocks.next(), blocks.next()) else {
+        panic!("Expected rules and pages separated by a blank line");
+    };
+
+    let rules: Vec<(usize, usize)> = parse::try_lines(rules, parse::pair).expect("Invalid rule");
+    let pages = pages
         .lines()
-        .map(|line| {
-            line.split(',')
-                .map(|s| s.parse::<usize>().unwrap())
-                .collect()
-        })
+        .map(|line| parse::ints(line).collect())
         .collect();
 
-    (rules, pages)
+    (rules.into_iter().collect(), pages)
 }
 
-fn is_ordered(rules: &Rules, pages: &[usize]) -> bool {
-    for (idx, &x) in pages.iter().enumerate() {
-        for &y in &pages[idx + 1..] {
-            if rules.contains(&(y, x)) {
-                return false;
-            }
-        }
-    }
-    true
+fn middle_element(slice: &[usize]) -> usize {
+    slice[slice.len() / 2]
 }
 
-fn reorder(rules: &Rules, pages: &mut [usize]) {
-    pages.sort_by(|a, b| {
-        if rules.contains(&(*a, *b)) {
-            Ordering::Less
-        } else if rules.contains(&(*b, *a)) {
-            Ordering::Greater
-        } else {
-            Ordering::Equal
-        }
-    });
+pub fn part1(input: &str) -> usize {
+    part1_with::<PrecedenceMatrix>(input)
 }
 
-fn middle_element(slice: &Vec<usize>) -> usize {
-    slice[slice.len() / 2]
-}
-
-fn part1(input: &str) -> usize {
-    let (rules, pages) = format_input(input);
+/// Part 1 with the rules stored as `R`, for the collections bench
+pub fn part1_with<R: Rules>(input: &str) -> usize {
+    let (rules, pages) = format_input::<R>(input);
 
     pages
         .iter()
-        .filter(|page_list| is_ordered(&rules, page_list))
-        .map(middle_element)
+        .filter(|page_list| rules.is_consistent_order(page_list))
+        .map(|page_list| middle_element(page_list))
         .sum()
 }
 
-fn part2(input: &str) -> usize {
-    let (rules, pages) = format_input(input);
+pub fn part2(input: &str) -> usize {
+    part2_with::<PrecedenceMatrix>(input)
+}
+
+/// Part 2 with the rules stored as `R`, for the collections bench
+pub fn part2_with<R: Rules>(input: &str) -> usize {
+    let (rules, pages) = format_input::<R>(input);
 
     pages
-        .into_iter()
-        .filter(|page_list| !is_ordered(&rules, page_list))
-        .map(|mut page_list| {
-            reorder(&rules, &mut page_list);
-            middle_element(&page_list)
+        .iter()
+        .filter(|page_list| !rules.is_consistent_order(page_list))
+        .map(|page_list| {
+            let ordered = rules
+                .toposort(page_list)
+                .expect("Rules for an update form a cycle");
+            middle_element(&ordered)
         })
         .sum()
 }
@@ -110,4 +103,18 @@ mod tests {
     fn test_part2() {
         assert_eq!(part2(EXAMPLE), 123);
     }
+
+    #[test]
+    fn test_hashed_rules() {
+        assert_eq!(part1_with::<OrderRules<usize>>(EXAMPLE), 143);
+        assert_eq!(part2_with::<OrderRules<usize>>(EXAMPLE), 123);
+    }
+
+    #[test]
+    fn test_page_without_rules() {
+        // 99 is in no rule, so it is larger than anything the matrix holds
+        let input = "47|53\n\n47,99,53\n53,99,47\n";
+        assert_eq!(part1(input), 99);
+        assert_eq!(part2(input), 47);
+    }
 }
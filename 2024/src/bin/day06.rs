@@ -1,44 +1,27 @@
-use std::{collections::HashSet, time::Instant};
+use std::collections::HashSet;
+use std::hash::BuildHasher;
 
-use aoc_common::grid::{Direction, Position, cell_at};
+use aoc_common::collections::FxBuildHasher;
+use aoc_common::grid::{BitGrid, Direction, Grid, Position};
+use aoc_common::parallel;
 
-fn main() {
-    let input = include_str!("../../data/inputs/06.txt");
+aoc_common::aoc_main!("06");
 
-    let start = Instant::now();
-    let part1_result = part1(input);
-    let part1_time = start.elapsed();
-    println!("Part 1: {} ({:?})", part1_result, part1_time);
-
-    let start = Instant::now();
-    let part2_result = part2(input);
-    let part2_time = start.elapsed();
-    println!("Part 2: {} ({:?})", part2_result, part2_time);
-
-    println!("\nTotal time: {:?}", part1_time + part2_time);
-}
-
-fn get_start_pos(grid: &[Vec<char>]) -> Position {
-    for (y, row) in grid.into_iter().enumerate() {
-        for (x, &ch) in row.into_iter().enumerate() {
-            if ch == '^' {
-                return Position::new(x as i64, y as i64);
-            }
-        }
-    }
-    unreachable!("Failed to find starting position")
+fn get_start_pos(grid: &Grid<char>) -> Position {
+    grid.find(&'^').expect("Failed to find starting position")
 }
 
-fn get_path(grid: &[Vec<char>]) -> HashSet<Position> {
+/// Cells the guard visits, in a set hashed by `S`
+fn get_path<S: BuildHasher + Default>(grid: &Grid<char>) -> HashSet<Position, S> {
     let mut pos = get_start_pos(grid);
     let mut direction = Direction::NORTH;
-    let mut visited = HashSet::new();
+    let mut visited = HashSet::default();
 
     loop {
         visited.insert(pos);
 
         let next_pos = pos.step(direction);
-        match cell_at(grid, next_pos) {
+        match grid.get(next_pos) {
             Some('#') => direction = direction.turn_right(),
             Some(_) => pos = next_pos,
             None => break,
@@ -48,77 +31,72 @@ fn get_path(grid: &[Vec<char>]) -> HashSet<Position> {
     visited
 }
 
-fn has_loop(
-    grid: &[Vec<char>],
-    obstacle: Position,
-    mut pos: Position,
-    visited: &mut [Vec<[bool; 4]>],
-) -> bool {
-    let mut direction = Direction::NORTH;
+/// Whether the guard walks in circles once `obstacle` is added
+///
+/// Only turns are recorded: the guard loops exactly when they turn at the
+/// same spot facing the same way twice.
+fn has_loop(grid: &Grid<char>, obstacle: Position, start: Position, turns: &mut BitGrid) -> bool {
+    turns.clear();
+    let (mut pos, mut direction) = (start, Direction::NORTH);
 
     loop {
-        let (row, col) = pos.row_col().expect("position out of bounds");
-
-        if visited[row][col][direction.index()] {
-            return true;
-        }
-        visited[row][col][direction.index()] = true;
-
         let next_pos = pos.step(direction);
-
-        if next_pos == obstacle {
-            direction = direction.turn_right();
-        } else {
-            match cell_at(&grid, next_pos) {
-                Some('#') => direction = direction.turn_right(),
-                Some(_) => pos = next_pos,
-                None => break,
+        match grid.get(next_pos) {
+            None => return false,
+            Some('#') => {}
+            Some(_) if next_pos == obstacle => {}
+            Some(_) => {
+                pos = next_pos;
+                continue;
             }
         }
+        if !turns.insert_in(pos, direction.index()) {
+            return true;
+        }
+        direction = direction.turn_right();
     }
-
-    false
 }
 
-/**
- * Clear the visited array to allow re-use. This makes it possible
- * to reuse the array instead of re-allocating the full array.
- */
-fn clear_visited(visited: &mut [Vec<[bool; 4]>]) {
-    for row in visited.iter_mut() {
-        for cell in row.iter_mut() {
-            *cell = [false; 4];
-        }
-    }
+fn parse_grid(input: &str) -> Grid<char> {
+    input.parse().expect("Failed to parse grid")
 }
 
-fn part1(input: &str) -> usize {
-    let grid = aoc_common::parse_char_grid(input);
-    get_path(&grid).len()
+pub fn part1(input: &str) -> usize {
+    part1_with::<FxBuildHasher>(input)
 }
 
-fn part2(input: &str) -> usize {
-    let grid = aoc_common::parse_char_grid(input);
-    let start = get_start_pos(&grid);
-    let path = get_path(&grid);
+/// Part 1 with the path hashed by `S`, for the collections bench
+pub fn part1_with<S: BuildHasher + Default>(input: &str) -> usize {
+    let grid = parse_grid(input);
+    get_path::<S>(&grid).len()
+}
 
-    // Allocate once, reuse for all iterations
-    let mut visited = vec![vec![[false; 4]; grid[0].len()]; grid.len()];
+pub fn part2(input: &str) -> usize {
+    part2_with::<FxBuildHasher>(input)
+}
 
-    path.into_iter()
+/// Part 2 with the path hashed by `S`, for the collections bench
+pub fn part2_with<S: BuildHasher + Default>(input: &str) -> usize {
+    let grid = parse_grid(input);
+    let start = get_start_pos(&grid);
+    let candidates: Vec<Position> = get_path::<S>(&grid)
+        .into_iter()
         .filter(|&pos| pos != start)
-        .filter(|&pos| {
-            let result = has_loop(&grid, pos, start, &mut visited);
-            // Clear visited for next iteration
-            clear_visited(&mut visited);
-            result
-        })
-        .count()
+        .collect();
+
+    // Each thread reuses one turn record across its candidates
+    parallel::par_count_with(
+        &candidates,
+        || BitGrid::like(&grid, 4),
+        |turns, &pos| has_loop(&grid, pos, start, turns),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_common::grid::Render;
+    use std::hash::RandomState;
 
     const EXAMPLE: &str = include_str!("../../data/examples/06.txt");
 
@@ -127,8 +105,32 @@ mod tests {
         assert_eq!(part1(EXAMPLE), 41);
     }
 
+    #[test]
+    fn test_path_snapshot() {
+        let grid = parse_grid(EXAMPLE);
+        let path = Render::new(&grid).mark(get_path::<FxBuildHasher>(&grid), 'X');
+        let expected = "\
+....#.....
+....XXXXX#
+....X...X.
+..#.X...X.
+..XXXXX#X.
+..X.X.X.X.
+.#XXXXXXX.
+.XXXXXXX#.
+#XXXXXXX..
+......#X..";
+        assert_eq!(path.to_string(), expected);
+    }
+
     #[test]
     fn test_part2() {
         assert_eq!(part2(EXAMPLE), 6);
     }
+
+    #[test]
+    fn test_sip_hashed_path() {
+        assert_eq!(part1_with::<RandomState>(EXAMPLE), 41);
+        assert_eq!(part2_with::<RandomState>(EXAMPLE), 6);
+    }
 }
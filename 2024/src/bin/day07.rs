@@ -1,22 +1,7 @@
-use std::time::Instant;
+use aoc_common::nums::strip_suffix_digits;
+use aoc_common::{parallel, parse};
 
-use aoc_common::nums::concat_numbers;
-
-fn main() {
-    let input = include_str!("../../data/inputs/07.txt");
-
-    let start = Instant::now();
-    let part1_result = part1(input);
-    let part1_time = start.elapsed();
-    println!("Part 1: {} ({:?})", part1_result, part1_time);
-
-    let start = Instant::now();
-    let part2_result = part2(input);
-    let part2_time = start.elapsed();
-    println!("Part 2: {} ({:?})", part2_result, part2_time);
-
-    println!("\nTotal time: {:?}", part1_time + part2_time);
-}
+aoc_common::aoc_main!("07");
 
 struct Equation {
     test_value: u64,
@@ -24,44 +9,42 @@ struct Equation {
 }
 
 fn parse_input(input: &str) -> Vec<Equation> {
-    input
-        .trim_end()
-        .lines()
-        .map(|line| {
-            let (left, right) = line.split_once(": ").unwrap();
-            let test_value = left.parse::<u64>().unwrap();
-            let numbers = right
-                .split_ascii_whitespace()
-                .map(|n| n.parse::<u64>().unwrap())
-                .collect();
-
-            Equation {
-                test_value,
-                numbers,
-            }
+    parse::try_lines(input.trim_end(), |line| {
+        let (left, right) = parse::split_pair(line, ": ")?;
+        let test_value = parse::int(left)?;
+        let numbers = parse::try_ints(right)
+            .collect::<Result<_, _>>()
+            .map_err(|e| e.offset(left.len() + 2))?;
+
+        Ok(Equation {
+            test_value,
+            numbers,
         })
-        .collect()
+    })
+    .expect("Invalid equation")
 }
 
-fn can_solve(
-    target: u64,
-    current_value: u64,
-    remaining_numbers: &[u64],
-    with_concat: bool,
-) -> bool {
-    if current_value > target {
-        return false;
-    }
-
-    match remaining_numbers {
-        [] => return target == current_value,
-        [next_num, rest @ ..] => {
-            can_solve(target, current_value + next_num, rest, with_concat)
-                || can_solve(target, current_value * next_num, rest, with_concat)
-                || (with_concat && {
-                    let concat = concat_numbers(current_value, *next_num);
-                    can_solve(target, concat, rest, with_concat)
-                })
+/// Work backwards from the target, undoing the last operation at each step
+///
+/// Undoing is only possible when the target allows it (large enough for `+`,
+/// divisible for `*`, ending in the right digits for `||`), which prunes far
+/// more than going forwards from the first number.
+fn can_solve(target: u64, numbers: &[u64], with_concat: bool) -> bool {
+    match numbers {
+        [] => false,
+        [first] => target == *first,
+        [rest @ .., last] => {
+            (target >= *last && can_solve(target - last, rest, with_concat))
+                || match *last {
+                    // Whatever `rest` makes, times 0 is 0
+                    0 => target == 0,
+                    last => {
+                        target.is_multiple_of(last) && can_solve(target / last, rest, with_concat)
+                    }
+                }
+                || (with_concat
+                    && strip_suffix_digits(target, *last)
+                        .is_some_and(|prefix| can_solve(prefix, rest, with_concat)))
         }
     }
 }
@@ -69,21 +52,25 @@ fn can_solve(
 fn part1(input: &str) -> u64 {
     let equations = parse_input(input);
 
-    equations
-        .into_iter()
-        .filter(|e| can_solve(e.test_value, e.numbers[0], &e.numbers[1..], false))
-        .map(|e| e.test_value)
-        .sum()
+    parallel::par_sum(&equations, |e| {
+        if can_solve(e.test_value, &e.numbers, false) {
+            e.test_value
+        } else {
+            0
+        }
+    })
 }
 
 fn part2(input: &str) -> u64 {
     let equations = parse_input(input);
 
-    equations
-        .into_iter()
-        .filter(|e| can_solve(e.test_value, e.numbers[0], &e.numbers[1..], true))
-        .map(|e| e.test_value)
-        .sum()
+    parallel::par_sum(&equations, |e| {
+        if can_solve(e.test_value, &e.numbers, true) {
+            e.test_value
+        } else {
+            0
+        }
+    })
 }
 
 #[cfg(test)]
@@ -101,4 +88,15 @@ mod tests {
     fn test_part2() {
         assert_eq!(part2(EXAMPLE), 11387);
     }
+
+    #[test]
+    fn test_zero_operands() {
+        assert!(can_solve(0, &[5, 0], false));
+        assert!(!can_solve(4, &[5, 0, 1], false));
+        assert!(can_solve(6, &[5, 0, 6], false));
+        assert!(can_solve(0, &[5, 0, 3], false));
+        assert!(can_solve(7, &[7, 0], false));
+        assert!(can_solve(50, &[5, 0], true));
+        assert!(!can_solve(1, &[0, 0], false));
+    }
 }
@@ -0,0 +1,331 @@
+use chrono::{DateTime, TimeZone, Utc};
+use colored::*;
+use std::io::IsTerminal;
+
+/// Puzzles unlock at midnight EST, which is 05:00 UTC
+const UNLOCK_HOUR_UTC: u32 = 5;
+
+/// Number of calendar cells per row (5×5 layout)
+const CELLS_PER_ROW: usize = 5;
+
+/// Everything the calendar needs to know about a single day
+#[derive(Debug, Clone, Copy)]
+pub struct CalendarDay {
+    pub day: u8,
+    pub part1: bool,
+    pub part2: bool,
+    pub has_solution: bool,
+    pub has_input: bool,
+}
+
+/// How the calendar should be drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    /// Use unicode glyphs (otherwise plain ASCII)
+    pub unicode: bool,
+    /// Use ANSI colours
+    pub color: bool,
+}
+
+impl Style {
+    /// Fancy output on a terminal, plain ASCII when piped or when `NO_COLOR` is set
+    pub fn detect() -> Self {
+        let fancy = std::io::stdout().is_terminal()
+            && std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty());
+        if fancy {
+            Self {
+                unicode: true,
+                color: true,
+            }
+        } else {
+            Self::plain()
+        }
+    }
+
+    pub fn plain() -> Self {
+        Self {
+            unicode: false,
+            color: false,
+        }
+    }
+}
+
+struct Glyphs {
+    star: &'static str,
+    no_star: &'static str,
+    solution: &'static str,
+    no_solution: &'static str,
+    input: &'static str,
+    no_input: &'static str,
+    locked: &'static str,
+}
+
+const UNICODE_GLYPHS: Glyphs = Glyphs {
+    star: "★",
+    no_star: "☆",
+    solution: "✓",
+    no_solution: "·",
+    input: "●",
+    no_input: "○",
+    locked: "🔒",
+};
+
+const ASCII_GLYPHS: Glyphs = Glyphs {
+    star: "*",
+    no_star: ".",
+    solution: "S",
+    no_solution: "-",
+    input: "I",
+    no_input: "-",
+    locked: "#",
+};
+
+impl Style {
+    fn glyphs(&self) -> &'static Glyphs {
+        if self.unicode {
+            &UNICODE_GLYPHS
+        } else {
+            &ASCII_GLYPHS
+        }
+    }
+
+    fn paint(&self, text: &str, color: Color) -> String {
+        if self.color {
+            text.color(color).to_string()
+        } else {
+            text.to_string()
+        }
+    }
+
+    fn dim(&self, text: &str) -> String {
+        if self.color {
+            text.dimmed().to_string()
+        } else {
+            text.to_string()
+        }
+    }
+
+    fn bold(&self, text: &str) -> String {
+        if self.color {
+            text.bold().to_string()
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// Get the moment a puzzle unlocks
+pub fn unlock_time(year: u16, day: u8) -> Option<DateTime<Utc>> {
+    Utc.with_ymd_and_hms(year as i32, 12, day as u32, UNLOCK_HOUR_UTC, 0, 0)
+        .single()
+}
+
+/// Find the next day to unlock and how long until it does
+pub fn next_unlock(year: u16, now: DateTime<Utc>) -> Option<(u8, chrono::Duration)> {
+    (1..=25).find_map(|day| {
+        let unlock = unlock_time(year, day)?;
+        (unlock > now).then(|| (day, unlock - now))
+    })
+}
+
+/// Format a countdown as e.g. "3d 04h 12m 09s"
+pub fn format_countdown(remaining: chrono::Duration) -> String {
+    let total = remaining.num_seconds().max(0);
+    let (days, hours) = (total / 86_400, (total % 86_400) / 3_600);
+    let (minutes, seconds) = ((total % 3_600) / 60, total % 60);
+
+    if days > 0 {
+        format!("{}d {:02}h {:02}m {:02}s", days, hours, minutes, seconds)
+    } else {
+        format!("{:02}h {:02}m {:02}s", hours, minutes, seconds)
+    }
+}
+
+fn render_cell(day: &CalendarDay, unlocked: bool, style: &Style) -> String {
+    let glyphs = style.glyphs();
+    let label = format!("{:02}", day.day);
+
+    if !unlocked {
+        // The lock emoji is two columns wide
+        let padding = if style.unicode { 4 } else { 5 };
+        return style.dim(&format!(
+            "{} {}{}",
+            label,
+            glyphs.locked,
+            " ".repeat(padding)
+        ));
+    }
+
+    let star = |earned: bool| {
+        if earned {
+            style.paint(glyphs.star, Color::Yellow)
+        } else {
+            style.dim(glyphs.no_star)
+        }
+    };
+
+    let solution = if day.has_solution {
+        style.paint(glyphs.solution, Color::Green)
+    } else {
+        style.dim(glyphs.no_solution)
+    };
+
+    let input = if day.has_input {
+        style.paint(glyphs.input, Color::Cyan)
+    } else {
+        style.dim(glyphs.no_input)
+    };
+
+    let label = if day.part1 && day.part2 {
+        style.paint(&label, Color::Yellow)
+    } else {
+        label
+    };
+
+    format!(
+        "{} {}{} {} {}",
+        label,
+        star(day.part1),
+        star(day.part2),
+        solution,
+        input
+    )
+}
+
+/// Render the 5×5 calendar, its legend and the next unlock countdown
+pub fn render(days: &[CalendarDay], year: u16, now: DateTime<Utc>, style: &Style) -> String {
+    let glyphs = style.glyphs();
+    let mut out = String::new();
+
+    for row in days.chunks(CELLS_PER_ROW) {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|day| {
+                let unlocked = unlock_time(year, day.day).is_none_or(|t| t <= now);
+                render_cell(day, unlocked, style)
+            })
+            .collect();
+        out.push_str("  ");
+        out.push_str(&cells.join("   "));
+        out.push('\n');
+    }
+
+    out.push('\n');
+    out.push_str(&style.dim(&format!(
+        "  {} star  {} solution  {} input  {} locked",
+        glyphs.star, glyphs.solution, glyphs.input, glyphs.locked
+    )));
+    out.push('\n');
+
+    match next_unlock(year, now) {
+        Some((day, remaining)) => out.push_str(&format!(
+            "\n  Day {} unlocks in {}\n",
+            day,
+            style.paint(&format_countdown(remaining), Color::Cyan)
+        )),
+        None => out.push_str(&style.dim("\n  All puzzles are unlocked\n")),
+    }
+
+    out
+}
+
+/// The totals printed under the calendar
+pub fn render_summary(stars: usize, solutions: usize, inputs: usize, style: &Style) -> String {
+    format!(
+        "{}\n  Total Stars: {}/50 {}\n  Local Solutions: {}/25\n  Local Inputs: {}/25\n",
+        style.bold("Progress Summary:"),
+        stars,
+        style.glyphs().star,
+        solutions,
+        inputs
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn days(f: impl Fn(u8) -> CalendarDay) -> Vec<CalendarDay> {
+        (1..=25).map(f).collect()
+    }
+
+    fn empty(day: u8) -> CalendarDay {
+        CalendarDay {
+            day,
+            part1: false,
+            part2: false,
+            has_solution: false,
+            has_input: false,
+        }
+    }
+
+    #[test]
+    fn test_unlock_time_is_midnight_est() {
+        let unlock = unlock_time(2024, 1).unwrap();
+        assert_eq!(unlock, Utc.with_ymd_and_hms(2024, 12, 1, 5, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_next_unlock() {
+        let now = Utc.with_ymd_and_hms(2024, 12, 6, 4, 30, 0).unwrap();
+        let (day, remaining) = next_unlock(2024, now).unwrap();
+        assert_eq!(day, 6);
+        assert_eq!(remaining.num_minutes(), 30);
+
+        let after = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        assert!(next_unlock(2024, after).is_none());
+    }
+
+    #[test]
+    fn test_format_countdown() {
+        assert_eq!(
+            format_countdown(chrono::Duration::seconds(3 * 86_400 + 4 * 3_600 + 61)),
+            "3d 04h 01m 01s"
+        );
+        assert_eq!(
+            format_countdown(chrono::Duration::seconds(59)),
+            "00h 00m 59s"
+        );
+    }
+
+    #[test]
+    fn test_render_plain_layout() {
+        let now = Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap();
+        let calendar = days(|day| CalendarDay {
+            part1: day <= 2,
+            part2: day == 1,
+            has_solution: day <= 3,
+            has_input: day <= 4,
+            ..empty(day)
+        });
+
+        let rendered = render(&calendar, 2024, now, &Style::plain());
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "  01 ** S I   02 *. S I   03 .. S I   04 .. - I   05 .. - -"
+        );
+        assert!(lines[4].starts_with("  21 .. - -"));
+        assert!(rendered.contains("All puzzles are unlocked"));
+        assert!(rendered.is_ascii());
+    }
+
+    #[test]
+    fn test_render_locked_days_and_countdown() {
+        let now = Utc.with_ymd_and_hms(2024, 12, 24, 12, 0, 0).unwrap();
+        let rendered = render(&days(empty), 2024, now, &Style::plain());
+
+        assert!(rendered.contains("24 .. - -"));
+        assert!(rendered.contains("25 #     \n"));
+        assert!(rendered.contains("Day 25 unlocks in 17h 00m 00s"));
+    }
+
+    #[test]
+    fn test_render_plain_summary() {
+        assert_eq!(
+            render_summary(7, 4, 5, &Style::plain()),
+            "Progress Summary:\n  Total Stars: 7/50 *\n  Local Solutions: 4/25\n  Local Inputs: 5/25\n"
+        );
+    }
+}
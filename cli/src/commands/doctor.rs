@@ -0,0 +1,422 @@
+use anyhow::{Context, Result};
+use colored::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::commands::{download, new};
+use crate::config::Config;
+use crate::utils;
+
+/// A problem found in the repository, with an optional automatic fix
+#[derive(Debug, PartialEq, Eq)]
+struct Finding {
+    message: String,
+    suggestion: String,
+    fix: Option<Fix>,
+}
+
+/// Fixes that are safe to apply without user input
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Fix {
+    /// Download a missing input (needs a session cookie)
+    DownloadInput { year: u16, day: u8 },
+    /// Add a year directory to the workspace `members`
+    AddToWorkspace { year: u16 },
+    /// Add a pattern for input files to `.gitignore`
+    IgnoreInputs,
+    /// Stop tracking input files (`git rm --cached`, the files are kept)
+    UntrackInputs { paths: Vec<PathBuf> },
+}
+
+pub fn run(config: &Config, fix: bool) -> Result<()> {
+    println!("{}", "Checking repository...".cyan());
+
+    let root = Path::new(".");
+    if !root.join("Cargo.toml").exists() {
+        anyhow::bail!("Workspace Cargo.toml not found. Are you in the repository root?");
+    }
+
+    let years = find_years(root)?;
+    let checks: [(&str, Vec<Finding>); 4] = [
+        (
+            "Inputs for every solution",
+            check_missing_inputs(root, &years),
+        ),
+        (
+            "Example files filled in",
+            check_empty_examples(root, &years),
+        ),
+        ("Years in workspace", check_workspace_members(root, &years)?),
+        ("Inputs kept out of git", check_git_inputs(root, &years)),
+    ];
+
+    let mut problems = 0;
+    let mut fixable = Vec::new();
+
+    for (name, findings) in checks {
+        if findings.is_empty() {
+            println!("  {} {}", "✓".green(), name);
+            continue;
+        }
+
+        println!("  {} {}", "✗".red(), name);
+        for finding in findings {
+            problems += 1;
+            println!("      {}", finding.message);
+            println!("      {} {}", "→".dimmed(), finding.suggestion.dimmed());
+            if let Some(f) = finding.fix {
+                fixable.push(f);
+            }
+        }
+    }
+
+    println!();
+    if problems == 0 {
+        println!("{}", "✓ No problems found".green().bold());
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!(
+            "{} problem(s) found, {} fixable with --fix",
+            problems,
+            fixable.len()
+        )
+        .yellow()
+        .bold()
+    );
+
+    if fix && !fixable.is_empty() {
+        println!();
+        println!("{}", "Applying fixes...".cyan());
+        for f in &fixable {
+            if let Err(e) = apply_fix(config, f) {
+                println!("  {} {}", "⚠".yellow(), e);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn apply_fix(config: &Config, fix: &Fix) -> Result<()> {
+    match fix {
+        Fix::DownloadInput { year, day } => download::run(config, *day, *year, false),
+        Fix::AddToWorkspace { year } => new::add_to_workspace(*year),
+        Fix::IgnoreInputs => {
+            let path = PathBuf::from(".gitignore");
+            let mut content = fs::read_to_string(&path).unwrap_or_default();
+            if !content.is_empty() && !content.ends_with('\n') {
+                content.push('\n');
+            }
+            content.push_str(INPUTS_IGNORE_PATTERN);
+            content.push('\n');
+            fs::write(&path, content).context("Failed to update .gitignore")?;
+            println!(
+                "  {} Added {} to .gitignore",
+                "✓".green(),
+                INPUTS_IGNORE_PATTERN
+            );
+            Ok(())
+        }
+        Fix::UntrackInputs { paths } => {
+            let status = Command::new("git")
+                .args(["rm", "--cached", "--quiet", "--"])
+                .args(paths)
+                .status()
+                .context("Failed to run git")?;
+            if !status.success() {
+                anyhow::bail!("git rm --cached failed");
+            }
+            println!(
+                "  {} Untracked {} input file(s), local copies kept",
+                "✓".green(),
+                paths.len()
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Gitignore pattern covering every year's input directory
+const INPUTS_IGNORE_PATTERN: &str = "**/data/inputs/";
+
+/// Find year directories (four-digit names with a Cargo.toml)
+fn find_years(root: &Path) -> Result<Vec<u16>> {
+    let mut years: Vec<u16> = fs::read_dir(root)?
+        .flatten()
+        .filter(|entry| entry.path().join("Cargo.toml").exists())
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let name = name.to_str()?;
+            (name.len() == 4).then(|| name.parse().ok()).flatten()
+        })
+        .collect();
+    years.sort_unstable();
+    Ok(years)
+}
+
+/// Days with a solution file in a year
+fn solution_days(root: &Path, year: u16) -> Vec<u8> {
+    (1..=25)
+        .filter(|&day| root.join(utils::paths::day_solution(year, day)).exists())
+        .collect()
+}
+
+fn check_missing_inputs(root: &Path, years: &[u16]) -> Vec<Finding> {
+    years
+        .iter()
+        .flat_map(|&year| {
+            solution_days(root, year)
+                .into_iter()
+                .filter(move |&day| !root.join(utils::paths::day_input(year, day)).exists())
+                .map(move |day| Finding {
+                    message: format!(
+                        "day{:02}.rs has no input at {}, so running it exits with \"Input file not found\"",
+                        day,
+                        utils::paths::day_input(year, day).display()
+                    ),
+                    suggestion: if root
+                        .join(utils::paths::day_encrypted_input(year, day))
+                        .exists()
+                    {
+                        format!("aoc inputs unlock -y {}", year)
+                    } else {
+                        format!("aoc download {} -y {}", day, year)
+                    },
+                    fix: Some(Fix::DownloadInput { year, day }),
+                })
+        })
+        .collect()
+}
+
+fn check_empty_examples(root: &Path, years: &[u16]) -> Vec<Finding> {
+    years
+        .iter()
+        .flat_map(|&year| {
+            solution_days(root, year)
+                .into_iter()
+                .filter(move |&day| {
+                    let path = root.join(utils::paths::day_example(year, day));
+                    fs::read_to_string(path).is_ok_and(|s| s.trim().is_empty())
+                })
+                .map(move |day| Finding {
+                    message: format!(
+                        "{} is empty",
+                        utils::paths::day_example(year, day).display()
+                    ),
+                    suggestion: format!(
+                        "Paste the example from https://adventofcode.com/{}/day/{}",
+                        year, day
+                    ),
+                    fix: None,
+                })
+        })
+        .collect()
+}
+
+/// Parse the single-line `members = [...]` array of the workspace Cargo.toml
+fn workspace_members(root: &Path) -> Result<Vec<String>> {
+    let content = fs::read_to_string(root.join("Cargo.toml"))
+        .context("Failed to read workspace Cargo.toml")?;
+
+    let line = content
+        .lines()
+        .find(|line| line.trim().starts_with("members = ["))
+        .context("Could not find 'members = [' in workspace Cargo.toml")?;
+
+    let (_, list) = line.split_once('[').unwrap();
+    Ok(list
+        .trim_end()
+        .trim_end_matches(']')
+        .split(',')
+        .map(|member| member.trim().trim_matches('"').to_string())
+        .filter(|member| !member.is_empty())
+        .collect())
+}
+
+fn check_workspace_members(root: &Path, years: &[u16]) -> Result<Vec<Finding>> {
+    let members = workspace_members(root)?;
+
+    Ok(years
+        .iter()
+        .filter(|year| !members.contains(&year.to_string()))
+        .map(|&year| Finding {
+            message: format!("{} is not in the workspace members", year),
+            suggestion: format!("Add \"{}\" to members in Cargo.toml", year),
+            fix: Some(Fix::AddToWorkspace { year }),
+        })
+        .collect())
+}
+
+fn check_git_inputs(root: &Path, years: &[u16]) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    // Not a git repository (or git not installed) - nothing to check
+    let Some(tracked) = git_output(root, &["ls-files", "--", "*/data/inputs/*"]) else {
+        return findings;
+    };
+
+    let unignored: Vec<u16> = years
+        .iter()
+        .copied()
+        .filter(|&year| {
+            let probe = utils::paths::day_input(year, 1);
+            let probe = probe.to_string_lossy();
+            git_output(root, &["check-ignore", "--no-index", "-q", &probe]).is_none()
+        })
+        .collect();
+
+    if !unignored.is_empty() {
+        let years: Vec<String> = unignored.iter().map(|y| y.to_string()).collect();
+        findings.push(Finding {
+            message: format!("Inputs are not gitignored for {}", years.join(", ")),
+            suggestion: format!("Add {} to .gitignore", INPUTS_IGNORE_PATTERN),
+            fix: Some(Fix::IgnoreInputs),
+        });
+    }
+
+    let paths: Vec<PathBuf> = tracked.lines().map(PathBuf::from).collect();
+    if !paths.is_empty() {
+        findings.push(Finding {
+            message: format!(
+                "{} input file(s) committed to git (AoC asks not to share inputs)",
+                paths.len()
+            ),
+            suggestion: "git rm --cached <files> (local copies are kept)".to_string(),
+            fix: Some(Fix::UntrackInputs { paths }),
+        });
+    }
+
+    findings
+}
+
+/// Run git in `root`, returning stdout on success
+fn git_output(root: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(args)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup_repo(members: &str) -> TempDir {
+        let dir = TempDir::new().expect("Failed to create temp dir");
+        let root = dir.path();
+        fs::write(
+            root.join("Cargo.toml"),
+            format!("[workspace]\nmembers = [{}]\n", members),
+        )
+        .unwrap();
+
+        for year in ["2023", "2024"] {
+            fs::create_dir_all(root.join(year).join("src/bin")).unwrap();
+            fs::create_dir_all(root.join(year).join("data/inputs")).unwrap();
+            fs::create_dir_all(root.join(year).join("data/examples")).unwrap();
+            fs::write(root.join(year).join("Cargo.toml"), "").unwrap();
+        }
+        fs::create_dir_all(root.join("common")).unwrap();
+        fs::write(root.join("common/Cargo.toml"), "").unwrap();
+
+        dir
+    }
+
+    fn add_day(root: &Path, year: u16, day: u8, input: Option<&str>, example: &str) {
+        fs::write(root.join(utils::paths::day_solution(year, day)), "").unwrap();
+        fs::write(root.join(utils::paths::day_example(year, day)), example).unwrap();
+        if let Some(input) = input {
+            fs::write(root.join(utils::paths::day_input(year, day)), input).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_find_years() {
+        let dir = setup_repo("\"common\"");
+        assert_eq!(find_years(dir.path()).unwrap(), vec![2023, 2024]);
+    }
+
+    #[test]
+    fn test_missing_inputs() {
+        let dir = setup_repo("");
+        add_day(dir.path(), 2024, 1, Some("1 2"), "1 2");
+        add_day(dir.path(), 2024, 2, None, "1 2");
+
+        let findings = check_missing_inputs(dir.path(), &[2024]);
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("Input file not found"));
+        assert_eq!(
+            findings[0].fix,
+            Some(Fix::DownloadInput { year: 2024, day: 2 })
+        );
+    }
+
+    #[test]
+    fn test_empty_examples() {
+        let dir = setup_repo("");
+        add_day(dir.path(), 2024, 1, Some("1 2"), "1 2");
+        add_day(dir.path(), 2024, 3, Some("1 2"), "\n");
+
+        let findings = check_empty_examples(dir.path(), &[2024]);
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("03.txt"));
+        assert_eq!(findings[0].fix, None);
+    }
+
+    #[test]
+    fn test_workspace_members() {
+        let dir = setup_repo("\"cli\", \"common\", \"2024\"");
+        assert_eq!(
+            workspace_members(dir.path()).unwrap(),
+            vec!["cli", "common", "2024"]
+        );
+
+        let findings = check_workspace_members(dir.path(), &[2023, 2024]).unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].fix, Some(Fix::AddToWorkspace { year: 2023 }));
+    }
+
+    #[test]
+    fn test_git_inputs() {
+        let dir = setup_repo("");
+        let root = dir.path();
+        add_day(root, 2024, 1, Some("1 2"), "1 2");
+
+        let git = |args: &[&str]| git_output(root, args).expect("git failed");
+        git(&["init", "--quiet"]);
+        git(&["add", "--force", "2024/data/inputs/01.txt"]);
+
+        let untrack = Fix::UntrackInputs {
+            paths: vec![PathBuf::from("2024/data/inputs/01.txt")],
+        };
+        let fixes = |findings: Vec<Finding>| -> Vec<Option<Fix>> {
+            findings.into_iter().map(|finding| finding.fix).collect()
+        };
+        assert_eq!(
+            fixes(check_git_inputs(root, &[2024])),
+            vec![Some(Fix::IgnoreInputs), Some(untrack.clone())]
+        );
+
+        // Ignoring the inputs doesn't untrack the one already committed
+        fs::write(
+            root.join(".gitignore"),
+            format!("{}\n", INPUTS_IGNORE_PATTERN),
+        )
+        .unwrap();
+        assert_eq!(fixes(check_git_inputs(root, &[2024])), vec![Some(untrack)]);
+
+        git(&["rm", "--cached", "--quiet", "2024/data/inputs/01.txt"]);
+        assert!(check_git_inputs(root, &[2024]).is_empty());
+    }
+}
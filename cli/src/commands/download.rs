@@ -3,12 +3,11 @@ use colored::*;
 use reqwest::header;
 use std::fs;
 
+use crate::commands::inputs;
 use crate::config::Config;
 use crate::utils;
 
 pub fn run(config: &Config, day: u8, year: u16, force: bool) -> Result<()> {
-    let session = config.require_session()?;
-
     println!(
         "{}",
         format!("Downloading input for day {} of year {}...", day, year).cyan()
@@ -31,6 +30,18 @@ pub fn run(config: &Config, day: u8, year: u16, force: bool) -> Result<()> {
         }
     }
 
+    // Restore from the committed encrypted copy instead of re-downloading
+    if config.encrypt_inputs() && !force && inputs::unlock_day(config, year, day)? {
+        println!(
+            "  {} {} from its encrypted copy",
+            "Unlocked".green(),
+            input_path.display()
+        );
+        return Ok(());
+    }
+
+    let session = config.require_session()?;
+
     // Download input
     let url = format!("https://adventofcode.com/{}/day/{}/input", year, day);
     let response = utils::http_client()
@@ -69,6 +80,11 @@ pub fn run(config: &Config, day: u8, year: u16, force: bool) -> Result<()> {
         fs::create_dir_all(parent)?;
     }
 
+    // Keep an encrypted copy that is safe to commit
+    if config.encrypt_inputs() {
+        inputs::store_encrypted(config, year, day, input.as_bytes())?;
+    }
+
     // Save input
     fs::write(&input_path, input)
         .context(format!("Failed to write input to {}", input_path.display()))?;
@@ -0,0 +1,190 @@
+use anyhow::{Context, Result};
+use colored::*;
+use std::fs;
+use std::path::Path;
+
+use crate::config::{self, Config};
+use crate::crypto;
+use crate::utils;
+
+/// Create a new inputs key, or import a shared one
+pub fn init(key: Option<&str>, force: bool) -> Result<()> {
+    let path = config::inputs_key_file_path()?;
+    if path.exists() && !force {
+        anyhow::bail!(
+            "An inputs key already exists at {}. Use --force to replace it.",
+            path.display()
+        );
+    }
+
+    let key = match key {
+        Some(key) => {
+            crypto::parse_key(key)?;
+            key.trim().to_string()
+        }
+        None => crypto::generate_key(),
+    };
+    config::save_inputs_key(&key)?;
+
+    println!("{} {}", "✓ Inputs key saved to".green(), path.display());
+    println!();
+    println!("{}", "Share it with teammates:".bold());
+    println!("  {} aoc inputs init --key {}", "$".dimmed(), key);
+    println!("{}", "Or in CI, set the secret:".bold());
+    println!("  AOC_INPUTS_KEY={}", key);
+    println!();
+    println!(
+        "{}",
+        "Keep this key private - anyone with it can read the committed inputs.".yellow()
+    );
+
+    Ok(())
+}
+
+/// Encrypt plaintext inputs into `data/encrypted` so they can be committed
+pub fn lock(config: &Config, year: u16) -> Result<()> {
+    let key = config.require_inputs_key()?;
+
+    println!(
+        "{}",
+        format!("Encrypting inputs for year {}...", year).cyan()
+    );
+
+    let mut locked = 0;
+    for day in 1..=25 {
+        let input_path = utils::paths::day_input(year, day);
+        let Ok(input) = fs::read(&input_path) else {
+            continue;
+        };
+        if input.is_empty() {
+            continue;
+        }
+
+        let encrypted_path = utils::paths::day_encrypted_input(year, day);
+        if !write_encrypted_if_changed(&key, &encrypted_path, &input)? {
+            continue;
+        }
+
+        println!("  {} {}", "Locked".green(), encrypted_path.display());
+        locked += 1;
+    }
+
+    println!("  {} {} input(s) encrypted", "✓".green(), locked);
+    Ok(())
+}
+
+/// Decrypt committed inputs into the gitignored `data/inputs`
+pub fn unlock(config: &Config, year: u16, force: bool) -> Result<()> {
+    let key = config.require_inputs_key()?;
+
+    println!(
+        "{}",
+        format!("Decrypting inputs for year {}...", year).cyan()
+    );
+
+    let mut unlocked = 0;
+    for day in 1..=25 {
+        let encrypted_path = utils::paths::day_encrypted_input(year, day);
+        if !encrypted_path.exists() {
+            continue;
+        }
+
+        let input = decrypt_file(&key, &encrypted_path)?;
+        let input_path = utils::paths::day_input(year, day);
+
+        match fs::read(&input_path) {
+            Ok(existing) if existing == input => continue,
+            Ok(existing) if !existing.is_empty() && !force => {
+                println!(
+                    "  {} {} differs from the encrypted copy, use --force to overwrite",
+                    "Skipped".yellow(),
+                    input_path.display()
+                );
+                continue;
+            }
+            _ => {}
+        }
+
+        write_file(&input_path, &input)?;
+        println!("  {} {}", "Unlocked".green(), input_path.display());
+        unlocked += 1;
+    }
+
+    println!("  {} {} input(s) decrypted", "✓".green(), unlocked);
+    Ok(())
+}
+
+/// Restore a single day's input from its encrypted copy, if there is one
+pub fn unlock_day(config: &Config, year: u16, day: u8) -> Result<bool> {
+    let encrypted_path = utils::paths::day_encrypted_input(year, day);
+    if !encrypted_path.exists() {
+        return Ok(false);
+    }
+
+    let key = config.require_inputs_key()?;
+    let input = decrypt_file(&key, &encrypted_path)?;
+    write_file(&utils::paths::day_input(year, day), &input)?;
+    Ok(true)
+}
+
+/// Store a freshly downloaded input encrypted, unless the committed copy
+/// already holds the same input
+pub fn store_encrypted(config: &Config, year: u16, day: u8, input: &[u8]) -> Result<()> {
+    let key = config.require_inputs_key()?;
+    let path = utils::paths::day_encrypted_input(year, day);
+    if write_encrypted_if_changed(&key, &path, input)? {
+        println!("  {} {}", "Encrypted".green(), path.display());
+    }
+    Ok(())
+}
+
+fn decrypt_file(key: &crypto::Key, path: &Path) -> Result<Vec<u8>> {
+    let data = fs::read(path).context(format!("Failed to read {}", path.display()))?;
+    crypto::decrypt(key, &data).context(format!("Failed to decrypt {}", path.display()))
+}
+
+fn write_encrypted(key: &crypto::Key, path: &Path, input: &[u8]) -> Result<()> {
+    write_file(path, &crypto::encrypt(key, input)?)
+}
+
+/// Encrypt `input` to `path` unless it already decrypts to the same bytes,
+/// returning whether it wrote
+///
+/// Every encryption uses a fresh nonce, so rewriting an unchanged input
+/// would still change the committed file.
+fn write_encrypted_if_changed(key: &crypto::Key, path: &Path, input: &[u8]) -> Result<bool> {
+    if decrypt_file(key, path).is_ok_and(|existing| existing == input) {
+        return Ok(false);
+    }
+    write_encrypted(key, path, input)?;
+    Ok(true)
+}
+
+fn write_file(path: &Path, data: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, data).context(format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_unchanged_input_is_not_rewritten() {
+        let dir = TempDir::new().expect("Failed to create temp dir");
+        let path = dir.path().join("01.txt.enc");
+        let key = crypto::parse_key(&crypto::generate_key()).unwrap();
+
+        assert!(write_encrypted_if_changed(&key, &path, b"1 2\n").unwrap());
+        let first = fs::read(&path).unwrap();
+
+        assert!(!write_encrypted_if_changed(&key, &path, b"1 2\n").unwrap());
+        assert_eq!(fs::read(&path).unwrap(), first);
+
+        assert!(write_encrypted_if_changed(&key, &path, b"3 4\n").unwrap());
+        assert_eq!(decrypt_file(&key, &path).unwrap(), b"3 4\n");
+    }
+}
@@ -1,4 +1,7 @@
+pub mod doctor;
 pub mod download;
+pub mod inputs;
 pub mod new;
+pub mod run;
 pub mod status;
 pub mod submit;
@@ -150,10 +150,11 @@ pub fn year(year: u16) -> Result<()> {
     if let Ok(entries) = fs::read_dir(year_dir.join("src/bin")) {
         for entry in entries.flatten() {
             let path = entry.path();
-            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                if name.starts_with("day") && name.ends_with(".rs") {
-                    let _ = fs::remove_file(&path);
-                }
+            if let Some(name) = path.file_name().and_then(|n| n.to_str())
+                && name.starts_with("day")
+                && name.ends_with(".rs")
+            {
+                let _ = fs::remove_file(&path);
             }
         }
     }
@@ -234,7 +235,7 @@ fn update_year_placeholders(year_dir: &Path, year: u16) -> Result<()> {
     Ok(())
 }
 
-fn add_to_workspace(year: u16) -> Result<()> {
+pub fn add_to_workspace(year: u16) -> Result<()> {
     let workspace_toml = std::path::PathBuf::from("Cargo.toml");
 
     if !workspace_toml.exists() {
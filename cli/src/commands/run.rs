@@ -0,0 +1,161 @@
+use anyhow::{Context, Result};
+use colored::*;
+use serde::Deserialize;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use aoc_common::runner::format_duration;
+
+/// JSON printed by a day binary run with `--json` (see `aoc_common::runner`)
+#[derive(Debug, Deserialize)]
+struct RunOutput {
+    day: u8,
+    repeat: u32,
+    parts: Vec<PartOutput>,
+    total_ns: u64,
+    /// Missing from solutions built before the field existed
+    #[serde(default)]
+    parallel: bool,
+    #[serde(default)]
+    threads: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PartOutput {
+    part: u8,
+    answer: String,
+    time_ns: u64,
+}
+
+pub fn run(
+    day: u8,
+    year: u16,
+    part: Option<u8>,
+    repeat: u32,
+    input: Option<&Path>,
+    parallel: bool,
+) -> Result<()> {
+    let solution_path = crate::utils::paths::day_solution(year, day);
+    if !solution_path.exists() {
+        anyhow::bail!(
+            "Solution file not found: {}. Run 'aoc new day {} -y {}' first.",
+            solution_path.display(),
+            day,
+            year
+        );
+    }
+
+    println!(
+        "{}",
+        format!("Running day {} of year {}...", day, year).cyan()
+    );
+
+    let mut command = Command::new("cargo");
+    command
+        .args(["run", "--release", "--quiet"])
+        .args(["-p", &format!("aoc-{}", year)])
+        .args(["--bin", &format!("day{:02}", day)]);
+    if parallel {
+        command.args(["--features", "parallel"]);
+    }
+    command.args(["--", "--json", "--repeat", &repeat.to_string()]);
+    if let Some(part) = part {
+        command.args(["--part", &part.to_string()]);
+    }
+    if let Some(input) = input {
+        command.arg("--input").arg(input);
+    }
+
+    let output = command
+        .stderr(Stdio::inherit())
+        .output()
+        .context("Failed to run cargo")?;
+
+    if !output.status.success() {
+        anyhow::bail!("Solution exited with {}", output.status);
+    }
+
+    let result = parse_output(&String::from_utf8_lossy(&output.stdout))?;
+
+    println!();
+    for part in &result.parts {
+        println!(
+            "  Part {}: {}  {}",
+            part.part,
+            part.answer.bold(),
+            format_duration(Duration::from_nanos(part.time_ns)).dimmed()
+        );
+    }
+    if result.parts.len() > 1 {
+        println!(
+            "  {} {}",
+            "Total:".dimmed(),
+            format_duration(Duration::from_nanos(result.total_ns))
+        );
+    }
+    if result.repeat > 1 {
+        println!(
+            "  {}",
+            format!("Average of {} runs", result.repeat).dimmed()
+        );
+    }
+    let build = match (result.parallel, result.threads) {
+        (true, Some(threads)) => format!("Parallel build ({} threads)", threads),
+        (true, None) => "Parallel build".to_string(),
+        (false, _) => "Serial build".to_string(),
+    };
+    println!("  {}", build.dimmed());
+
+    Ok(())
+}
+
+/// Find the JSON result among anything else the solution printed
+fn parse_output(stdout: &str) -> Result<RunOutput> {
+    let line = stdout
+        .lines()
+        .rev()
+        .find(|line| line.trim_start().starts_with('{'))
+        .context("Solution did not print a JSON result. Does it use aoc_main!?")?;
+
+    let output: RunOutput =
+        serde_json::from_str(line).context("Failed to parse the solution's JSON output")?;
+    if output.day == 0 {
+        anyhow::bail!("Solution reported an invalid day");
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_output() {
+        let stdout = "debug line\n{\"day\":1,\"repeat\":3,\"parts\":[{\"part\":1,\"answer\":\"11\",\"time_ns\":1200},{\"part\":2,\"answer\":\"31\",\"time_ns\":800}],\"total_ns\":2000}\n";
+        let output = parse_output(stdout).unwrap();
+
+        assert_eq!(output.day, 1);
+        assert_eq!(output.repeat, 3);
+        assert_eq!(output.parts.len(), 2);
+        assert_eq!(output.parts[1].answer, "31");
+        assert_eq!(output.parts[0].time_ns, 1200);
+        assert_eq!(output.total_ns, 2000);
+        assert!(!output.parallel);
+    }
+
+    #[test]
+    fn test_parse_parallel_output() {
+        let stdout =
+            "{\"day\":6,\"repeat\":1,\"parts\":[],\"total_ns\":0,\"parallel\":true,\"threads\":8}";
+        let output = parse_output(stdout).unwrap();
+
+        assert!(output.parallel);
+        assert_eq!(output.threads, Some(8));
+    }
+
+    #[test]
+    fn test_parse_output_without_json() {
+        assert!(parse_output("Part 1: 11 (1.20µs)\n").is_err());
+    }
+}
@@ -5,6 +5,7 @@ use scraper::Html;
 use std::collections::HashSet;
 use std::fs;
 
+use crate::calendar::{self, CalendarDay};
 use crate::config::Config;
 use crate::utils;
 
@@ -14,6 +15,7 @@ struct DayStatus {
     part1: bool,
     part2: bool,
     has_solution: bool,
+    has_input: bool,
 }
 
 pub fn run(config: &Config, year: u16, update_readme: bool) -> Result<()> {
@@ -35,22 +37,25 @@ fn run_with_options(config: &Config, year: u16, update_readme: bool, quiet: bool
     }
 
     // Fetch completion status from AOC website
-    let completed = fetch_completed_stars(&session, year)?;
+    let completed = fetch_completed_stars(session, year)?;
 
     // Check which solutions exist locally
     let local_solutions = find_local_solutions(year)?;
+    let local_inputs = find_local_inputs(year);
 
     // Build status for all 25 days
     let mut statuses = Vec::new();
     for day in 1..=25 {
         let (part1, part2) = completed.get(&day).copied().unwrap_or((false, false));
         let has_solution = local_solutions.contains(&day);
+        let has_input = local_inputs.contains(&day);
 
         statuses.push(DayStatus {
             day,
             part1,
             part2,
             has_solution,
+            has_input,
         });
     }
 
@@ -60,11 +65,36 @@ fn run_with_options(config: &Config, year: u16, update_readme: bool, quiet: bool
         .map(|s| s.part1 as usize + s.part2 as usize)
         .sum();
 
-    // Display summary
+    // Display calendar and summary
     if !quiet {
-        println!("\n{}", "Progress Summary:".bold());
-        println!("  Total Stars: {}/50 ⭐", total_stars);
-        println!("  Local Solutions: {}/25", local_solutions.len());
+        let style = calendar::Style::detect();
+
+        let days: Vec<CalendarDay> = statuses
+            .iter()
+            .map(|s| CalendarDay {
+                day: s.day,
+                part1: s.part1,
+                part2: s.part2,
+                has_solution: s.has_solution,
+                has_input: s.has_input,
+            })
+            .collect();
+
+        println!();
+        print!(
+            "{}",
+            calendar::render(&days, year, chrono::Utc::now(), &style)
+        );
+
+        print!(
+            "\n{}",
+            calendar::render_summary(
+                total_stars,
+                local_solutions.len(),
+                local_inputs.len(),
+                &style
+            )
+        );
     }
 
     // Update README if requested
@@ -153,13 +183,10 @@ fn find_local_solutions(year: u16) -> Result<HashSet<u8>> {
 
         if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
             // Match files like "day01.rs", "day02.rs", etc.
-            if name.starts_with("day") && name.ends_with(".rs") {
-                if let Some(day_str) = name.strip_prefix("day").and_then(|s| s.strip_suffix(".rs"))
-                {
-                    if let Ok(day) = day_str.parse::<u8>() {
-                        solutions.insert(day);
-                    }
-                }
+            if let Some(day_str) = name.strip_prefix("day").and_then(|s| s.strip_suffix(".rs"))
+                && let Ok(day) = day_str.parse::<u8>()
+            {
+                solutions.insert(day);
             }
         }
     }
@@ -167,6 +194,13 @@ fn find_local_solutions(year: u16) -> Result<HashSet<u8>> {
     Ok(solutions)
 }
 
+/// Find days whose input has been downloaded (non-empty input file)
+fn find_local_inputs(year: u16) -> HashSet<u8> {
+    (1..=25)
+        .filter(|&day| fs::metadata(utils::paths::day_input(year, day)).is_ok_and(|m| m.len() > 0))
+        .collect()
+}
+
 fn update_readme_table(
     readme_path: &std::path::PathBuf,
     statuses: &[DayStatus],
@@ -1,15 +1,23 @@
 use anyhow::{Context, Result};
 use std::fs;
-use std::path::PathBuf;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::crypto;
 
 pub struct Config {
     pub session: Option<String>,
+    pub inputs_key: Option<String>,
 }
 
 impl Config {
     pub fn load() -> Result<Self> {
         let session = load_session().ok();
-        Ok(Config { session })
+        let inputs_key = load_inputs_key().ok();
+        Ok(Config {
+            session,
+            inputs_key,
+        })
     }
 
     pub fn require_session(&self) -> Result<&str> {
@@ -17,6 +25,18 @@ impl Config {
             "No session cookie found. Please run:\n  aoc auth <session_cookie>\n\nGet your session cookie from https://adventofcode.com (F12 → Application → Cookies → session)"
         )
     }
+
+    /// Inputs are stored encrypted whenever an inputs key is configured
+    pub fn encrypt_inputs(&self) -> bool {
+        self.inputs_key.is_some()
+    }
+
+    pub fn require_inputs_key(&self) -> Result<crypto::Key> {
+        let key = self.inputs_key.as_deref().context(
+            "No inputs key found. Please run:\n  aoc inputs init\n\nOr import a shared key with `aoc inputs init --key <key>` or the AOC_INPUTS_KEY environment variable"
+        )?;
+        crypto::parse_key(key)
+    }
 }
 
 fn config_dir() -> Result<PathBuf> {
@@ -57,6 +77,63 @@ pub fn save_session(session: &str) -> Result<()> {
     Ok(())
 }
 
+pub fn inputs_key_file_path() -> Result<PathBuf> {
+    let mut path = config_dir()?;
+    path.push("inputs.key");
+    Ok(path)
+}
+
+fn load_inputs_key() -> Result<String> {
+    // Environment variable first, so CI can pass the key as a secret
+    if let Some(key) = std::env::var("AOC_INPUTS_KEY").ok().and_then(non_empty) {
+        return Ok(key);
+    }
+
+    let path = inputs_key_file_path()?;
+    if path.exists() {
+        let key = fs::read_to_string(&path).context("Failed to read inputs key file")?;
+        if let Some(key) = non_empty(key) {
+            return Ok(key);
+        }
+    }
+
+    anyhow::bail!("No inputs key found")
+}
+
+/// Trimmed `value`, or `None` if only whitespace is left
+///
+/// An empty key (e.g. a CI secret that isn't set) counts as no key at all
+/// rather than turning encryption on with a key that can't be parsed.
+fn non_empty(value: String) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+pub fn save_inputs_key(key: &str) -> Result<()> {
+    let path = inputs_key_file_path()?;
+    write_private(&path, key.trim()).context("Failed to write inputs key file")?;
+    Ok(())
+}
+
+/// Write `contents` to a file only the current user can read (on Unix)
+fn write_private(path: &Path, contents: &str) -> io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        let file = options.open(path)?;
+        // `mode` only applies to new files, so tighten an existing one too
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+        (&file).write_all(contents.as_bytes())
+    }
+    #[cfg(not(unix))]
+    {
+        options.open(path)?.write_all(contents.as_bytes())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -95,7 +172,10 @@ mod tests {
 
     #[test]
     fn test_config_require_session_fails_without_session() {
-        let config = Config { session: None };
+        let config = Config {
+            session: None,
+            inputs_key: None,
+        };
         let result = config.require_session();
         assert!(result.is_err());
         assert!(result
@@ -108,9 +188,48 @@ mod tests {
     fn test_config_require_session_succeeds_with_session() {
         let config = Config {
             session: Some("valid_session".to_string()),
+            inputs_key: None,
         };
         let result = config.require_session();
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "valid_session");
     }
+
+    #[test]
+    fn test_config_require_inputs_key() {
+        let mut config = Config {
+            session: None,
+            inputs_key: None,
+        };
+        assert!(!config.encrypt_inputs());
+        assert!(config.require_inputs_key().is_err());
+
+        config.inputs_key = Some(crypto::generate_key());
+        assert!(config.encrypt_inputs());
+        assert!(config.require_inputs_key().is_ok());
+    }
+
+    #[test]
+    fn test_empty_inputs_key_is_unset() {
+        assert_eq!(non_empty(String::new()), None);
+        assert_eq!(non_empty(" \n\t".to_string()), None);
+        assert_eq!(non_empty(" abc\n".to_string()), Some("abc".to_string()));
+    }
+
+    #[test]
+    fn test_write_private() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("inputs.key");
+        fs::write(&path, "a much longer old key").unwrap();
+
+        write_private(&path, "new key").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new key");
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
 }
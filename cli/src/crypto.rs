@@ -0,0 +1,113 @@
+use anyhow::{Context, Result};
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+
+pub use chacha20poly1305::Key;
+
+/// Header identifying files written by `encrypt`
+const MAGIC: &[u8] = b"aoc-enc1";
+
+/// ChaCha20-Poly1305 nonce length in bytes
+const NONCE_LEN: usize = 12;
+
+/// Generate a new random key, hex encoded for storage and sharing
+pub fn generate_key() -> String {
+    encode_hex(&ChaCha20Poly1305::generate_key(&mut OsRng))
+}
+
+/// Parse a hex encoded 32-byte key
+pub fn parse_key(hex: &str) -> Result<Key> {
+    let bytes = decode_hex(hex.trim()).context("Inputs key is not valid hex")?;
+    if bytes.len() != 32 {
+        anyhow::bail!(
+            "Inputs key must be 32 bytes (64 hex characters), got {}",
+            bytes.len()
+        );
+    }
+    Ok(*Key::from_slice(&bytes))
+}
+
+/// Encrypt with a fresh random nonce: `MAGIC || nonce || ciphertext`
+pub fn encrypt(key: &Key, plaintext: &[u8]) -> Result<Vec<u8>> {
+    let cipher = ChaCha20Poly1305::new(key);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| anyhow::anyhow!("Failed to encrypt input"))?;
+
+    let mut out = Vec::with_capacity(MAGIC.len() + NONCE_LEN + ciphertext.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+/// Decrypt data produced by `encrypt`, failing on a wrong key or tampering
+pub fn decrypt(key: &Key, data: &[u8]) -> Result<Vec<u8>> {
+    let rest = data
+        .strip_prefix(MAGIC)
+        .context("Not an encrypted input file")?;
+    if rest.len() < NONCE_LEN {
+        anyhow::bail!("Encrypted input file is truncated");
+    }
+
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    ChaCha20Poly1305::new(key)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow::anyhow!("Failed to decrypt input (wrong key or corrupted file)"))
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let key = parse_key(&generate_key()).unwrap();
+        let encrypted = encrypt(&key, b"1 2 3\n4 5 6\n").unwrap();
+
+        assert!(encrypted.starts_with(MAGIC));
+        assert_eq!(decrypt(&key, &encrypted).unwrap(), b"1 2 3\n4 5 6\n");
+    }
+
+    #[test]
+    fn test_wrong_key_fails() {
+        let key = parse_key(&generate_key()).unwrap();
+        let other = parse_key(&generate_key()).unwrap();
+        let encrypted = encrypt(&key, b"secret").unwrap();
+
+        assert!(decrypt(&other, &encrypted).is_err());
+    }
+
+    #[test]
+    fn test_tampered_data_fails() {
+        let key = parse_key(&generate_key()).unwrap();
+        let mut encrypted = encrypt(&key, b"secret").unwrap();
+        *encrypted.last_mut().unwrap() ^= 1;
+
+        assert!(decrypt(&key, &encrypted).is_err());
+        assert!(decrypt(&key, b"plain text").is_err());
+    }
+
+    #[test]
+    fn test_parse_key() {
+        assert!(parse_key("abc").is_err());
+        assert!(parse_key("zz".repeat(32).as_str()).is_err());
+        assert!(parse_key(&"ab".repeat(16)).is_err());
+        assert!(parse_key(&format!("{}\n", "0f".repeat(32))).is_ok());
+    }
+}
@@ -2,11 +2,13 @@ use anyhow::Result;
 use chrono::Datelike;
 use clap::{Parser, Subcommand};
 
+mod calendar;
 mod commands;
 mod config;
+mod crypto;
 mod utils;
 
-use commands::{download, new, status, submit};
+use commands::{doctor, download, inputs, new, run, status, submit};
 use config::Config;
 
 #[derive(Parser)]
@@ -40,6 +42,33 @@ enum Commands {
         force: bool,
     },
 
+    /// Run a solution in release mode and show its answers and timings
+    Run {
+        /// Day number (1-25)
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Year (defaults to current year)
+        #[arg(short, long)]
+        year: Option<u16>,
+
+        /// Only run one part
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Run each part N times and report the average time
+        #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        repeat: u32,
+
+        /// Use another input file instead of data/inputs/NN.txt
+        #[arg(short, long)]
+        input: Option<std::path::PathBuf>,
+
+        /// Build with the `parallel` feature so `par_*` helpers use all cores
+        #[arg(long)]
+        parallel: bool,
+    },
+
     /// Submit solution
     Submit {
         /// Day number (1-25)
@@ -69,6 +98,19 @@ enum Commands {
         update: bool,
     },
 
+    /// Manage encrypted inputs
+    Inputs {
+        #[command(subcommand)]
+        command: InputsCommands,
+    },
+
+    /// Check the repository for common problems
+    Doctor {
+        /// Apply the safe fixes
+        #[arg(long)]
+        fix: bool,
+    },
+
     /// Set session cookie
     Auth {
         /// Session cookie value
@@ -96,6 +138,38 @@ enum NewCommands {
     },
 }
 
+#[derive(Subcommand)]
+enum InputsCommands {
+    /// Create the key used to encrypt inputs (or import a shared one)
+    Init {
+        /// Existing key to import instead of generating a new one
+        #[arg(long)]
+        key: Option<String>,
+
+        /// Replace an existing key
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Encrypt local inputs into data/encrypted for committing
+    Lock {
+        /// Year (defaults to current year)
+        #[arg(short, long)]
+        year: Option<u16>,
+    },
+
+    /// Decrypt committed inputs into data/inputs
+    Unlock {
+        /// Year (defaults to current year)
+        #[arg(short, long)]
+        year: Option<u16>,
+
+        /// Overwrite local inputs that differ from the encrypted copy
+        #[arg(short, long)]
+        force: bool,
+    },
+}
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
     let config = Config::load()?;
@@ -114,6 +188,17 @@ fn main() -> Result<()> {
             let year = determine_year(year)?;
             download::run(&config, day, year, force)?;
         }
+        Commands::Run {
+            day,
+            year,
+            part,
+            repeat,
+            input,
+            parallel,
+        } => {
+            let year = determine_year(year)?;
+            run::run(day, year, part, repeat, input.as_deref(), parallel)?;
+        }
         Commands::Submit {
             day,
             part,
@@ -127,6 +212,22 @@ fn main() -> Result<()> {
             let year = determine_year(year)?;
             status::run(&config, year, update)?;
         }
+        Commands::Inputs { command } => match command {
+            InputsCommands::Init { key, force } => {
+                inputs::init(key.as_deref(), force)?;
+            }
+            InputsCommands::Lock { year } => {
+                let year = determine_year(year)?;
+                inputs::lock(&config, year)?;
+            }
+            InputsCommands::Unlock { year, force } => {
+                let year = determine_year(year)?;
+                inputs::unlock(&config, year, force)?;
+            }
+        },
+        Commands::Doctor { fix } => {
+            doctor::run(&config, fix)?;
+        }
         Commands::Auth { session } => {
             config::save_session(&session)?;
             println!("Session cookie saved successfully!");
@@ -67,6 +67,11 @@ pub mod paths {
         year_dir(year).join(format!("data/inputs/{:02}.txt", day))
     }
 
+    /// Get the path to a day's encrypted input file (safe to commit)
+    pub fn day_encrypted_input(year: u16, day: u8) -> PathBuf {
+        year_dir(year).join(format!("data/encrypted/{:02}.txt.enc", day))
+    }
+
     /// Get the path to a day's example file
     pub fn day_example(year: u16, day: u8) -> PathBuf {
         year_dir(year).join(format!("data/examples/{:02}.txt", day))
@@ -0,0 +1,116 @@
+// Compare SipHash collections with `aoc_common::collections` on day-shaped work
+//
+// The data is synthetic: a random 130x130 lab for day 6's guard walk and
+// random rules for day 5's lookups, sized like the real inputs. The numbers
+// show the relative cost of the collections, not timings of the days.
+//
+//   cargo bench -p aoc-common --bench collections
+
+use std::collections::HashSet;
+use std::hint::black_box;
+
+use aoc_common::collections::{BitSet, FxHashSet};
+use aoc_common::grid::{Direction, Grid, Position};
+use criterion::{Criterion, criterion_group, criterion_main};
+
+mod common;
+use common::xorshift;
+
+/// Day 6 shaped input: a 130x130 floor with scattered obstacles
+fn lab() -> (Grid<bool>, Position) {
+    let mut next = xorshift(0x9e37_79b9);
+    let cells = (0..130 * 130).map(|_| next().is_multiple_of(50)).collect();
+    let mut grid = Grid::from_vec(130, 130, cells).unwrap();
+    let start = Position::new(65, 65);
+    grid[start] = false;
+    (grid, start)
+}
+
+/// Walk the guard until they leave (or 20 000 steps), recording each cell
+fn walk(grid: &Grid<bool>, start: Position, mut visit: impl FnMut(Position)) {
+    let (mut pos, mut direction) = (start, Direction::NORTH);
+    for _ in 0..20_000 {
+        visit(pos);
+        let next = pos.step(direction);
+        match grid.get(next) {
+            Some(true) => direction = direction.turn_right(),
+            Some(false) => pos = next,
+            None => return,
+        }
+    }
+}
+
+fn bench_guard_path(c: &mut Criterion) {
+    let (grid, start) = lab();
+    let mut group = c.benchmark_group("collections/guard_path");
+
+    group.bench_function("HashSet", |b| {
+        b.iter(|| {
+            let mut seen = HashSet::new();
+            walk(black_box(&grid), start, |pos| {
+                seen.insert(pos);
+            });
+            seen.len()
+        })
+    });
+
+    group.bench_function("FxHashSet", |b| {
+        b.iter(|| {
+            let mut seen = FxHashSet::default();
+            walk(black_box(&grid), start, |pos| {
+                seen.insert(pos);
+            });
+            seen.len()
+        })
+    });
+
+    group.bench_function("BitSet", |b| {
+        b.iter(|| {
+            let mut seen = BitSet::new(grid.width() * grid.height());
+            walk(black_box(&grid), start, |pos| {
+                seen.insert(pos.y as usize * grid.width() + pos.x as usize);
+            });
+            seen.len()
+        })
+    });
+
+    group.finish();
+}
+
+/// Day 5 shaped work: look up "a before b" rules between two-digit pages
+fn bench_rule_lookups(c: &mut Criterion) {
+    let mut next = xorshift(0x2545_f491);
+    let rules: Vec<(usize, usize)> = (0..1_200)
+        .map(|_| (10 + next() as usize % 90, 10 + next() as usize % 90))
+        .collect();
+    let queries: Vec<(usize, usize)> = (0..5_000)
+        .map(|_| (10 + next() as usize % 90, 10 + next() as usize % 90))
+        .collect();
+    let mut group = c.benchmark_group("collections/rule_lookups");
+
+    let sip: HashSet<(usize, usize)> = rules.iter().copied().collect();
+    group.bench_function("HashSet", |b| {
+        b.iter(|| queries.iter().filter(|q| sip.contains(q)).count())
+    });
+
+    let fx: FxHashSet<(usize, usize)> = rules.iter().copied().collect();
+    group.bench_function("FxHashSet", |b| {
+        b.iter(|| queries.iter().filter(|q| fx.contains(q)).count())
+    });
+
+    let mut bits = BitSet::new(100 * 100);
+    bits.extend(rules.iter().map(|&(a, b)| a * 100 + b));
+    group.bench_function("BitSet", |b| {
+        b.iter(|| {
+            queries
+                .iter()
+                .filter(|&&(a, b)| bits.contains(a * 100 + b))
+                .count()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_guard_path, bench_rule_lookups);
+criterion_main!(benches);
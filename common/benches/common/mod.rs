@@ -0,0 +1,12 @@
+// Helpers shared by the benches, which build their inputs from a fixed seed
+
+/// A xorshift64 generator, so every run benches the same data
+pub fn xorshift(seed: u64) -> impl FnMut() -> u64 {
+    let mut state = seed;
+    move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    }
+}
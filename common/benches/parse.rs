@@ -0,0 +1,148 @@
+// Compare `aoc_common::parse` with the `split`/`parse::<T>()` code days used before
+//
+//   cargo bench -p aoc-common --bench parse
+
+use std::hint::black_box;
+
+use aoc_common::parse;
+use criterion::{Criterion, criterion_group, criterion_main};
+
+mod common;
+use common::xorshift;
+
+/// Day 7 shaped input: "<target>: <n> <n> ..."
+fn equations() -> String {
+    let mut next = xorshift(0x2545_f491);
+
+    (0..850)
+        .map(|_| {
+            let count = 3 + next() % 9;
+            let numbers: Vec<String> = (0..count).map(|_| (1 + next() % 999).to_string()).collect();
+            format!("{}: {}", next() % 100_000_000_000, numbers.join(" "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Day 5 shaped input: rules, a blank line, then page lists
+fn rules_and_pages() -> String {
+    let rules: Vec<String> = (0..1_200)
+        .map(|i| format!("{}|{}", 10 + i % 89, 11 + (i * 7) % 88))
+        .collect();
+    let pages: Vec<String> = (0..200)
+        .map(|i| {
+            let pages: Vec<String> = (0..5 + i % 19)
+                .map(|j| (10 + (i + j * 3) % 89).to_string())
+                .collect();
+            pages.join(",")
+        })
+        .collect();
+    format!("{}\n\n{}\n", rules.join("\n"), pages.join("\n"))
+}
+
+fn bench_equations(c: &mut Criterion) {
+    let input = equations();
+    let mut group = c.benchmark_group("parse/equations");
+
+    group.bench_function("split_parse", |b| {
+        b.iter(|| {
+            black_box(&input)
+                .lines()
+                .map(|line| {
+                    let (left, right) = line.split_once(": ").unwrap();
+                    let target = left.parse::<u64>().unwrap();
+                    let numbers: Vec<u64> = right
+                        .split_ascii_whitespace()
+                        .map(|n| n.parse().unwrap())
+                        .collect();
+                    (target, numbers)
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+
+    // Scanning alone, without collecting into vectors
+    group.bench_function("split_parse_sum", |b| {
+        b.iter(|| {
+            black_box(&input)
+                .lines()
+                .flat_map(|line| line.split([' ', ':']).filter(|s| !s.is_empty()))
+                .map(|n| n.parse::<u64>().unwrap())
+                .sum::<u64>()
+        })
+    });
+
+    group.bench_function("ints_sum", |b| {
+        b.iter(|| {
+            black_box(&input)
+                .lines()
+                .flat_map(parse::ints::<u64>)
+                .sum::<u64>()
+        })
+    });
+
+    group.bench_function("ints", |b| {
+        b.iter(|| {
+            black_box(&input)
+                .lines()
+                .map(|line| parse::ints::<u64>(line).collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        })
+    });
+
+    group.bench_function("try_lines", |b| {
+        b.iter(|| {
+            parse::try_lines(black_box(&input), |line| {
+                let (left, right) = parse::split_pair(line, ": ")?;
+                let numbers = parse::try_ints::<u64>(right).collect::<Result<Vec<_>, _>>()?;
+                Ok((parse::int::<u64>(left)?, numbers))
+            })
+            .unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_rules_and_pages(c: &mut Criterion) {
+    let input = rules_and_pages();
+    let mut group = c.benchmark_group("parse/rules_and_pages");
+
+    group.bench_function("split_parse", |b| {
+        b.iter(|| {
+            let (left, right) = black_box(&input).split_once("\n\n").unwrap();
+            let rules: Vec<(usize, usize)> = left
+                .lines()
+                .map(|line| {
+                    let (a, b) = line.split_once('|').unwrap();
+                    (a.parse().unwrap(), b.parse().unwrap())
+                })
+                .collect();
+            let pages: Vec<Vec<usize>> = right
+                .lines()
+                .map(|line| line.split(',').map(|s| s.parse().unwrap()).collect())
+                .collect();
+            (rules, pages)
+        })
+    });
+
+    group.bench_function("parse", |b| {
+        b.iter(|| {
+            let mut blocks = parse::split_blocks(black_box(&input));
+            let rules: Vec<(usize, usize)> =
+                parse::try_lines(blocks.next().unwrap(), parse::pair).unwrap();
+            let pages: Vec<Vec<usize>> = blocks
+                .next()
+                .unwrap()
+                .lines()
+                .map(|line| parse::ints(line).collect())
+                .collect();
+            (rules, pages)
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_equations, bench_rules_and_pages);
+criterion_main!(benches);
@@ -15,13 +15,19 @@
 /// Requirements:
 /// - Day modules must be exposed in lib.rs: `pub mod day01;`
 /// - Each day must have `pub fn part1(input: &str)` and `pub fn part2(input: &str)`
-/// - Input files at `data/inputs/{day}.txt`
+/// - Input files at `data/inputs/{day}.txt` (read at runtime)
 #[macro_export]
+#[allow(clippy::crate_in_macro_def)] // `crate` must resolve to the calling year crate
 macro_rules! benchmark {
     ($day:ident) => {
         fn $day(c: &mut criterion::Criterion) {
             let day_num = stringify!($day).trim_start_matches("day");
-            let input = include_str!(concat!("../data/inputs/", day_num, ".txt"));
+            // Criterion owns the command line, so always read the default input
+            let input = &$crate::input::read_or_exit(
+                env!("CARGO_MANIFEST_DIR"),
+                day_num,
+                &$crate::input::Source::Default,
+            );
 
             c.bench_function(&format!("{}/part1", stringify!($day)), |b| {
                 b.iter(|| {
@@ -0,0 +1,412 @@
+// Faster sets and maps for puzzle-sized keys
+//
+// The standard `HashMap` uses SipHash, which resists collision attacks that
+// puzzle inputs never mount. `FxHashMap`/`FxHashSet` swap in FxHash, a few
+// multiply-and-rotate steps per word. When keys are small integers (grid
+// indices, page numbers, node IDs) a `BitSet` or `DenseMap` skips hashing
+// altogether and indexes straight into a vector.
+//
+//   cargo bench -p aoc-common --bench collections
+
+use std::fmt;
+
+pub use rustc_hash::{FxBuildHasher, FxHashMap, FxHashSet, FxHasher};
+
+/// Set of integers below a fixed size, one bit each
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    words: Vec<u64>,
+    size: usize,
+}
+
+impl BitSet {
+    /// Empty set that can hold `0..size`
+    pub fn new(size: usize) -> Self {
+        Self {
+            words: vec![0; size.div_ceil(64)],
+            size,
+        }
+    }
+
+    /// One past the largest value the set can hold
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    fn bit(&self, value: usize) -> (usize, u64) {
+        assert!(
+            value < self.size,
+            "{} is outside a bit set of size {}",
+            value,
+            self.size
+        );
+        (value / 64, 1 << (value % 64))
+    }
+
+    /// Add `value`, returning whether it was newly added
+    ///
+    /// Panics if `value` is not below `size`.
+    pub fn insert(&mut self, value: usize) -> bool {
+        let (word, mask) = self.bit(value);
+        let added = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        added
+    }
+
+    /// Remove `value`, returning whether it was present
+    pub fn remove(&mut self, value: usize) -> bool {
+        let (word, mask) = self.bit(value);
+        let present = self.words[word] & mask != 0;
+        self.words[word] &= !mask;
+        present
+    }
+
+    /// Whether `value` is in the set; values beyond `size` never are
+    pub fn contains(&self, value: usize) -> bool {
+        value < self.size && self.words[value / 64] & (1 << (value % 64)) != 0
+    }
+
+    /// Number of values in the set
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Values in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut rest = word;
+            std::iter::from_fn(move || {
+                if rest == 0 {
+                    return None;
+                }
+                let bit = rest.trailing_zeros() as usize;
+                rest &= rest - 1;
+                Some(i * 64 + bit)
+            })
+        })
+    }
+
+    /// Add every value of `other`; the sets must have the same size
+    pub fn union_with(&mut self, other: &BitSet) {
+        assert_eq!(self.size, other.size, "bit sets differ in size");
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a |= b;
+        }
+    }
+
+    /// Keep only values also in `other`; the sets must have the same size
+    pub fn intersect_with(&mut self, other: &BitSet) {
+        assert_eq!(self.size, other.size, "bit sets differ in size");
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a &= b;
+        }
+    }
+}
+
+impl fmt::Debug for BitSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl Extend<usize> for BitSet {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+/// Keys that map onto small vector indices
+pub trait DenseKey: Copy {
+    fn to_index(self) -> usize;
+    fn from_index(index: usize) -> Self;
+}
+
+macro_rules! impl_dense_key {
+    ($($t:ty),*) => {
+        $(
+            impl DenseKey for $t {
+                fn to_index(self) -> usize {
+                    self as usize
+                }
+
+                fn from_index(index: usize) -> Self {
+                    index as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_dense_key!(u8, u16, u32, usize);
+
+impl DenseKey for char {
+    fn to_index(self) -> usize {
+        self as usize
+    }
+
+    fn from_index(index: usize) -> Self {
+        char::from_u32(index as u32).expect("index came from a char")
+    }
+}
+
+/// Map from small integer keys to values, stored in a vector indexed by key
+///
+/// Memory grows with the largest key, not the number of entries.
+#[derive(Clone, PartialEq, Eq)]
+pub struct DenseMap<K, V> {
+    slots: Vec<Option<V>>,
+    len: usize,
+    _key: std::marker::PhantomData<K>,
+}
+
+impl<K: DenseKey, V> DenseMap<K, V> {
+    pub fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            len: 0,
+            _key: std::marker::PhantomData,
+        }
+    }
+
+    /// Empty map with room for keys below `size` before reallocating
+    pub fn with_capacity(size: usize) -> Self {
+        let mut map = Self::new();
+        map.slots.resize_with(size, || None);
+        map
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn contains_key(&self, key: K) -> bool {
+        self.get(key).is_some()
+    }
+
+    pub fn get(&self, key: K) -> Option<&V> {
+        self.slots.get(key.to_index())?.as_ref()
+    }
+
+    pub fn get_mut(&mut self, key: K) -> Option<&mut V> {
+        self.slots.get_mut(key.to_index())?.as_mut()
+    }
+
+    fn slot(&mut self, key: K) -> &mut Option<V> {
+        let index = key.to_index();
+        if index >= self.slots.len() {
+            self.slots.resize_with(index + 1, || None);
+        }
+        &mut self.slots[index]
+    }
+
+    /// Store `value` under `key`, returning the previous value
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let previous = self.slot(key).replace(value);
+        if previous.is_none() {
+            self.len += 1;
+        }
+        previous
+    }
+
+    pub fn remove(&mut self, key: K) -> Option<V> {
+        let removed = self.slots.get_mut(key.to_index())?.take();
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    /// The value under `key`, inserting `f()` first if there is none
+    pub fn get_or_insert_with(&mut self, key: K, f: impl FnOnce() -> V) -> &mut V {
+        let slot = self.slot(key);
+        if slot.is_none() {
+            *slot = Some(f());
+            self.len += 1;
+        }
+        self.slots[key.to_index()].as_mut().unwrap()
+    }
+
+    /// Entries in ascending key order
+    pub fn iter(&self) -> impl Iterator<Item = (K, &V)> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(i, slot)| slot.as_ref().map(|value| (K::from_index(i), value)))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = K> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.slots.iter().flatten()
+    }
+
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.len = 0;
+    }
+}
+
+impl<K: DenseKey, V> Default for DenseMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: DenseKey + fmt::Debug, V: fmt::Debug> fmt::Debug for DenseMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: DenseKey, V> FromIterator<(K, V)> for DenseMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        map
+    }
+}
+
+impl<K: DenseKey, V> std::ops::Index<K> for DenseMap<K, V> {
+    type Output = V;
+
+    fn index(&self, key: K) -> &V {
+        self.get(key)
+            .unwrap_or_else(|| panic!("no entry for key {}", key.to_index()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fx_aliases() {
+        let mut seen: FxHashSet<(i64, i64)> = FxHashSet::default();
+        assert!(seen.insert((1, 2)));
+        assert!(!seen.insert((1, 2)));
+
+        let mut counts: FxHashMap<&str, u32> = FxHashMap::default();
+        *counts.entry("a").or_default() += 2;
+        assert_eq!(counts["a"], 2);
+    }
+
+    #[test]
+    fn test_bit_set() {
+        let mut set = BitSet::new(130);
+        assert!(set.insert(0));
+        assert!(set.insert(64));
+        assert!(set.insert(129));
+        assert!(!set.insert(64));
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(129));
+        assert!(!set.contains(500));
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0, 64, 129]);
+
+        assert!(set.remove(64));
+        assert!(!set.remove(64));
+        assert_eq!(format!("{:?}", set), "{0, 129}");
+
+        let mut other = BitSet::new(130);
+        other.extend([0, 5]);
+        let mut union = set.clone();
+        union.union_with(&other);
+        assert_eq!(union.iter().collect::<Vec<_>>(), [0, 5, 129]);
+        set.intersect_with(&other);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0]);
+
+        set.clear();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bit_set_insert_out_of_range_panics() {
+        BitSet::new(10).insert(10);
+    }
+
+    #[test]
+    fn test_dense_map() {
+        let mut map: DenseMap<u8, &str> = DenseMap::new();
+        assert_eq!(map.insert(47, "a"), None);
+        assert_eq!(map.insert(3, "b"), None);
+        assert_eq!(map.insert(47, "c"), Some("a"));
+        assert_eq!(map.len(), 2);
+        assert_eq!(map[47], "c");
+        assert_eq!(map.get(200), None);
+        assert_eq!(map.keys().collect::<Vec<_>>(), [3, 47]);
+
+        *map.get_or_insert_with(9, || "d") = "e";
+        assert_eq!(map.get_or_insert_with(9, || "f"), &"e");
+        assert_eq!(map.remove(3), Some("b"));
+        assert_eq!(map.remove(3), None);
+        assert_eq!(format!("{:?}", map), r#"{9: "e", 47: "c"}"#);
+        assert_eq!(map.values().count(), 2);
+    }
+
+    #[test]
+    fn test_dense_map_char_keys() {
+        let counts: DenseMap<char, usize> = "hello".chars().fold(DenseMap::new(), |mut map, ch| {
+            *map.get_or_insert_with(ch, || 0) += 1;
+            map
+        });
+        assert_eq!(counts['l'], 2);
+        assert_eq!(counts.keys().collect::<String>(), "ehlo");
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::{BTreeMap, BTreeSet};
+
+    proptest! {
+        #[test]
+        fn test_bit_set_matches_btree_set(ops in prop::collection::vec((any::<bool>(), 0usize..200), 0..100)) {
+            let mut set = BitSet::new(200);
+            let mut model = BTreeSet::new();
+            for (insert, value) in ops {
+                if insert {
+                    prop_assert_eq!(set.insert(value), model.insert(value));
+                } else {
+                    prop_assert_eq!(set.remove(value), model.remove(&value));
+                }
+            }
+            prop_assert_eq!(set.len(), model.len());
+            prop_assert!(set.iter().eq(model.iter().copied()));
+        }
+
+        #[test]
+        fn test_dense_map_matches_btree_map(ops in prop::collection::vec((any::<bool>(), 0u16..300, any::<u8>()), 0..100)) {
+            let mut map = DenseMap::new();
+            let mut model = BTreeMap::new();
+            for (insert, key, value) in ops {
+                if insert {
+                    prop_assert_eq!(map.insert(key, value), model.insert(key, value));
+                } else {
+                    prop_assert_eq!(map.remove(key), model.remove(&key));
+                }
+            }
+            prop_assert_eq!(map.len(), model.len());
+            prop_assert!(map.iter().eq(model.iter().map(|(&k, v)| (k, v))));
+        }
+    }
+}
@@ -0,0 +1,245 @@
+// Cycle detection for deterministic state sequences
+//
+// A sequence `x0, x1 = step(x0), x2 = step(x1), ...` over a finite state
+// space eventually repeats. The detectors here return `(mu, lambda)`: the
+// index of the first state on the cycle, and the cycle length, so that
+// `x(i) == x(i + lambda)` for every `i >= mu`.
+//
+// `floyd` and `brent` only need `Eq + Clone` and constant memory, but call
+// `step` more than once per state. `find_cycle` calls it once per state and
+// remembers every state in a hash map.
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::hash::Hash;
+
+/// Floyd's tortoise and hare
+pub fn floyd<S: Eq + Clone>(start: S, mut step: impl FnMut(&S) -> S) -> (usize, usize) {
+    let mut tortoise = step(&start);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    // The meeting point is a multiple of lambda from the start, so walking one
+    // pointer from the start and one from there meets at the cycle entry
+    let mut mu = 0;
+    tortoise = start;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        mu += 1;
+    }
+
+    let mut lambda = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        lambda += 1;
+    }
+
+    (mu, lambda)
+}
+
+/// Brent's algorithm, usually fewer `step` calls than Floyd's
+pub fn brent<S: Eq + Clone>(start: S, mut step: impl FnMut(&S) -> S) -> (usize, usize) {
+    try_brent(start, |state| Some(step(state))).expect("infinite sequences always cycle")
+}
+
+/// Brent's algorithm for sequences that can end: `step` returns `None` when
+/// there is no next state, and then there is no cycle either
+pub fn try_brent<S: Eq + Clone>(
+    start: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+) -> Option<(usize, usize)> {
+    let lambda = cycle_length(start.clone(), &mut step)?;
+
+    // Start the hare lambda steps ahead, then move both until they meet
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..lambda {
+        hare = step(&hare).expect("step is not deterministic");
+    }
+
+    let mut mu = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise).expect("step is not deterministic");
+        hare = step(&hare).expect("step is not deterministic");
+        mu += 1;
+    }
+
+    Some((mu, lambda))
+}
+
+/// Whether the sequence from `start` loops forever instead of ending
+///
+/// Only runs the first phase of Brent's algorithm, so it is cheaper than
+/// `try_brent` when `mu` is not needed.
+pub fn has_cycle<S: Eq + Clone>(start: S, mut step: impl FnMut(&S) -> Option<S>) -> bool {
+    cycle_length(start, &mut step).is_some()
+}
+
+/// First phase of Brent's algorithm: the cycle length, or `None` if the
+/// sequence ends
+fn cycle_length<S: Eq + Clone>(start: S, step: &mut impl FnMut(&S) -> Option<S>) -> Option<usize> {
+    let mut power = 1;
+    let mut lambda = 1;
+    let mut hare = step(&start)?;
+    let mut tortoise = start;
+
+    while tortoise != hare {
+        if power == lambda {
+            tortoise = hare.clone();
+            power *= 2;
+            lambda = 0;
+        }
+        hare = step(&hare)?;
+        lambda += 1;
+    }
+
+    Some(lambda)
+}
+
+/// Hash-based detection, calling `step` exactly once per distinct state
+pub fn find_cycle<S: Hash + Eq + Clone>(start: S, mut step: impl FnMut(&S) -> S) -> (usize, usize) {
+    let mut seen = HashMap::new();
+    let mut state = start;
+
+    for index in 0.. {
+        match seen.entry(state) {
+            Entry::Occupied(entry) => {
+                let mu = *entry.get();
+                return (mu, index - mu);
+            }
+            Entry::Vacant(entry) => {
+                let next = step(entry.key());
+                entry.insert(index);
+                state = next;
+            }
+        }
+    }
+
+    unreachable!()
+}
+
+/// The state after `n` steps, skipping ahead once the cycle is found
+///
+/// ```
+/// use aoc_common::cycle::nth_state;
+///
+/// // 1, 2, 4, 8, 16, 11, 1, 2, ... repeats every 6 steps
+/// assert_eq!(nth_state(1u64, |&x| x * 2 % 21, 1_000_000_000), 16);
+/// ```
+pub fn nth_state<S: Hash + Eq + Clone>(start: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = start;
+
+    while states.len() < n {
+        if let Some(&mu) = seen.get(&state) {
+            let lambda = states.len() - mu;
+            let index = mu + (n - mu) % lambda;
+            return states.swap_remove(index);
+        }
+
+        let next = step(&state);
+        seen.insert(state.clone(), states.len());
+        states.push(state);
+        state = next;
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 2, so mu = 2 and lambda = 4
+    fn rho(x: &u32) -> u32 {
+        if *x == 5 { 2 } else { x + 1 }
+    }
+
+    #[test]
+    fn test_detectors_agree() {
+        assert_eq!(floyd(0, rho), (2, 4));
+        assert_eq!(brent(0, rho), (2, 4));
+        assert_eq!(find_cycle(0, rho), (2, 4));
+    }
+
+    #[test]
+    fn test_start_on_cycle() {
+        assert_eq!(floyd(3, rho), (0, 4));
+        assert_eq!(brent(3, rho), (0, 4));
+        assert_eq!(find_cycle(3, rho), (0, 4));
+    }
+
+    #[test]
+    fn test_fixed_point() {
+        assert_eq!(floyd(7, |&x| x), (0, 1));
+        assert_eq!(brent(7, |&x| x), (0, 1));
+        assert_eq!(find_cycle(7, |&x| x), (0, 1));
+    }
+
+    #[test]
+    fn test_sequences_that_end() {
+        let ends = |x: &u32| (*x < 10).then_some(x + 1);
+        assert_eq!(try_brent(0, ends), None);
+        assert!(!has_cycle(0, ends));
+
+        assert_eq!(try_brent(0, |x| Some(rho(x))), Some((2, 4)));
+        assert!(has_cycle(0, |x| Some(rho(x))));
+    }
+
+    #[test]
+    fn test_nth_state() {
+        assert_eq!(nth_state(0, rho, 0), 0);
+        assert_eq!(nth_state(0, rho, 1), 1);
+        assert_eq!(nth_state(0, rho, 5), 5);
+        assert_eq!(nth_state(0, rho, 6), 2);
+        assert_eq!(nth_state(0, rho, 1_000_000_000), 4);
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// A random function on `0..n`, as a lookup table
+    fn function() -> impl Strategy<Value = Vec<usize>> {
+        (1usize..40).prop_flat_map(|n| prop::collection::vec(0..n, n))
+    }
+
+    proptest! {
+        #[test]
+        fn test_detectors_agree(next in function(), start in 0usize..40) {
+            let start = start % next.len();
+            let step = |&x: &usize| next[x];
+
+            let expected = find_cycle(start, step);
+            prop_assert_eq!(floyd(start, step), expected);
+            prop_assert_eq!(brent(start, step), expected);
+        }
+
+        #[test]
+        fn test_nth_state_matches_iteration(next in function(), n in 0usize..200) {
+            let step = |&x: &usize| next[x];
+            let naive = (0..n).fold(0, |x, _| step(&x));
+            prop_assert_eq!(nth_state(0, step, n), naive);
+        }
+
+        #[test]
+        fn test_cycle_definition(next in function()) {
+            let step = |&x: &usize| next[x];
+            let (mu, lambda) = find_cycle(0, step);
+
+            prop_assert_eq!(nth_state(0, step, mu), nth_state(0, step, mu + lambda));
+            if mu > 0 {
+                prop_assert_ne!(nth_state(0, step, mu - 1), nth_state(0, step, mu - 1 + lambda));
+            }
+        }
+    }
+}
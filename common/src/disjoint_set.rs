@@ -0,0 +1,443 @@
+// Union-find over indices, over arbitrary keys, and over grid cells
+//
+// `DisjointSet` uses union by rank and path compression, so every operation
+// is effectively constant time. `KeyedDisjointSet` maps keys such as
+// `Position` or `String` to indices, and `label_regions` uses it to number
+// the connected regions of a `Grid`.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::grid::{Grid, Position};
+
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl DisjointSet {
+    /// `n` elements, each in its own component
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+            size: vec![1; n],
+            components: n,
+        }
+    }
+
+    /// Add a new element in its own component and return its index
+    pub fn push(&mut self) -> usize {
+        let index = self.parent.len();
+        self.parent.push(index);
+        self.rank.push(0);
+        self.size.push(1);
+        self.components += 1;
+        index
+    }
+
+    /// Number of elements
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// The representative of `x`'s component
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        // Point everything on the way straight at the root
+        let mut node = x;
+        while self.parent[node] != root {
+            node = std::mem::replace(&mut self.parent[node], root);
+        }
+
+        root
+    }
+
+    /// Merge the components of `a` and `b`, returning false if they were
+    /// already the same
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        if self.rank[a] < self.rank[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        if self.rank[a] == self.rank[b] {
+            self.rank[a] += 1;
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.components -= 1;
+        true
+    }
+
+    /// Whether `a` and `b` are in the same component
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Number of elements in `x`'s component
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Number of components
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// Size of every component, largest first
+    pub fn component_sizes(&self) -> Vec<usize> {
+        let mut sizes: Vec<usize> = (0..self.len())
+            .filter(|&x| self.parent[x] == x)
+            .map(|root| self.size[root])
+            .collect();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes
+    }
+
+    /// The elements of every component, each group and the groups ordered by
+    /// their smallest element
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let mut group_of_root = HashMap::new();
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+
+        for x in 0..self.len() {
+            let root = self.find(x);
+            let group = *group_of_root.entry(root).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[group].push(x);
+        }
+
+        groups
+    }
+}
+
+/// `DisjointSet` over hashable keys, added on first use
+///
+/// ```
+/// use aoc_common::disjoint_set::KeyedDisjointSet;
+///
+/// let mut circuits = KeyedDisjointSet::new();
+/// circuits.union("kh", "tc");
+/// circuits.union("qp", "kh");
+/// circuits.insert("de");
+///
+/// assert!(circuits.same(&"tc", &"qp"));
+/// assert_eq!(circuits.size(&"kh"), Some(3));
+/// assert_eq!(circuits.component_count(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct KeyedDisjointSet<K> {
+    index: HashMap<K, usize>,
+    keys: Vec<K>,
+    sets: DisjointSet,
+}
+
+impl<K> Default for KeyedDisjointSet<K> {
+    fn default() -> Self {
+        Self {
+            index: HashMap::new(),
+            keys: Vec::new(),
+            sets: DisjointSet::new(0),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone> KeyedDisjointSet<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `key` in its own component if it isn't known yet, and return its index
+    pub fn insert(&mut self, key: K) -> usize {
+        if let Some(&index) = self.index.get(&key) {
+            return index;
+        }
+        let index = self.sets.push();
+        self.index.insert(key.clone(), index);
+        self.keys.push(key);
+        index
+    }
+
+    /// Merge the components of `a` and `b`, adding either if needed
+    pub fn union(&mut self, a: K, b: K) -> bool {
+        let (a, b) = (self.insert(a), self.insert(b));
+        self.sets.union(a, b)
+    }
+
+    /// The representative key of `key`'s component
+    pub fn find(&mut self, key: &K) -> Option<&K> {
+        let index = *self.index.get(key)?;
+        let root = self.sets.find(index);
+        Some(&self.keys[root])
+    }
+
+    /// Whether both keys are known and in the same component
+    pub fn same(&mut self, a: &K, b: &K) -> bool {
+        match (self.index.get(a), self.index.get(b)) {
+            (Some(&a), Some(&b)) => self.sets.same(a, b),
+            _ => false,
+        }
+    }
+
+    /// Number of keys in `key`'s component
+    pub fn size(&mut self, key: &K) -> Option<usize> {
+        let index = *self.index.get(key)?;
+        Some(self.sets.size(index))
+    }
+
+    /// Number of keys
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.sets.component_count()
+    }
+
+    /// Size of every component, largest first
+    pub fn component_sizes(&self) -> Vec<usize> {
+        self.sets.component_sizes()
+    }
+
+    /// The keys of every component, in insertion order
+    pub fn groups(&mut self) -> Vec<Vec<K>> {
+        self.sets
+            .groups()
+            .into_iter()
+            .map(|group| group.into_iter().map(|i| self.keys[i].clone()).collect())
+            .collect()
+    }
+}
+
+/// Connected regions of a grid, numbered in reading order of their first cell
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regions {
+    /// The region number of every cell
+    pub labels: Grid<usize>,
+    /// Number of cells in each region
+    pub sizes: Vec<usize>,
+}
+
+impl Regions {
+    pub fn count(&self) -> usize {
+        self.sizes.len()
+    }
+
+    /// The cells of region `label`
+    pub fn cells(&self, label: usize) -> impl Iterator<Item = Position> + '_ {
+        self.labels
+            .iter()
+            .filter(move |&(_, &l)| l == label)
+            .map(|(pos, _)| pos)
+    }
+}
+
+/// Group orthogonally adjacent cells for which `same` holds into regions
+///
+/// ```
+/// use aoc_common::disjoint_set::label_regions;
+/// use aoc_common::grid::Grid;
+///
+/// let garden: Grid<char> = "AAB\nACB\nCCB".parse().unwrap();
+/// let regions = label_regions(&garden, |a, b| a == b);
+///
+/// assert_eq!(regions.count(), 3);
+/// assert_eq!(regions.sizes, [3, 3, 3]);
+/// ```
+pub fn label_regions<T>(grid: &Grid<T>, same: impl Fn(&T, &T) -> bool) -> Regions {
+    let (width, height) = (grid.width(), grid.height());
+    let mut sets = DisjointSet::new(width * height);
+
+    for (pos, cell) in grid.iter() {
+        let index = pos.y as usize * width + pos.x as usize;
+        for next in [
+            Position::new(pos.x + 1, pos.y),
+            Position::new(pos.x, pos.y + 1),
+        ] {
+            if let Some(other) = grid.get(next)
+                && same(cell, other)
+            {
+                sets.union(index, next.y as usize * width + next.x as usize);
+            }
+        }
+    }
+
+    let mut label_of_root = HashMap::new();
+    let mut sizes = Vec::new();
+    let labels = (0..width * height)
+        .map(|index| {
+            let root = sets.find(index);
+            *label_of_root.entry(root).or_insert_with(|| {
+                sizes.push(sets.size[root]);
+                sizes.len() - 1
+            })
+        })
+        .collect();
+
+    Regions {
+        labels: Grid::from_vec(width, height, labels).unwrap(),
+        sizes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_and_find() {
+        let mut sets = DisjointSet::new(6);
+        assert_eq!(sets.component_count(), 6);
+
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 2));
+
+        assert!(sets.same(0, 3));
+        assert!(!sets.same(0, 4));
+        assert_eq!(sets.size(2), 4);
+        assert_eq!(sets.component_count(), 3);
+        assert_eq!(sets.component_sizes(), [4, 1, 1]);
+        assert_eq!(sets.groups(), [vec![0, 1, 2, 3], vec![4], vec![5]]);
+    }
+
+    #[test]
+    fn test_push() {
+        let mut sets = DisjointSet::new(1);
+        let x = sets.push();
+        assert_eq!(x, 1);
+        assert_eq!(sets.component_count(), 2);
+        sets.union(0, x);
+        assert_eq!(sets.len(), 2);
+        assert_eq!(sets.component_count(), 1);
+    }
+
+    #[test]
+    fn test_keyed_with_positions() {
+        let mut sets = KeyedDisjointSet::new();
+        sets.union(Position::new(0, 0), Position::new(0, 1));
+        sets.union(Position::new(5, 5), Position::new(5, 6));
+
+        assert!(sets.same(&Position::new(0, 1), &Position::new(0, 0)));
+        assert!(!sets.same(&Position::new(0, 0), &Position::new(5, 5)));
+        assert!(!sets.same(&Position::new(0, 0), &Position::new(9, 9)));
+        assert_eq!(sets.size(&Position::new(9, 9)), None);
+        assert_eq!(sets.find(&Position::new(9, 9)), None);
+        assert_eq!(sets.len(), 4);
+    }
+
+    #[test]
+    fn test_keyed_groups_with_strings() {
+        let mut sets = KeyedDisjointSet::new();
+        for (a, b) in [("a", "b"), ("c", "d"), ("b", "e")] {
+            sets.union(a.to_string(), b.to_string());
+        }
+
+        assert_eq!(
+            sets.groups(),
+            [vec!["a", "b", "e"], vec!["c", "d"]]
+                .map(|g| g.into_iter().map(String::from).collect::<Vec<_>>())
+        );
+        assert_eq!(sets.component_sizes(), [3, 2]);
+    }
+
+    #[test]
+    fn test_label_regions() {
+        let garden: Grid<char> = "\
+RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE"
+            .parse()
+            .unwrap();
+        let regions = label_regions(&garden, |a, b| a == b);
+
+        // Two separate regions of both C and I
+        assert_eq!(regions.count(), 11);
+        assert_eq!(regions.sizes[0], 12);
+        assert_eq!(regions.labels[Position::new(0, 0)], 0);
+        assert_eq!(regions.cells(0).count(), 12);
+        assert!(regions.cells(0).all(|pos| garden[pos] == 'R'));
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Components by repeatedly relabelling until nothing changes
+    fn naive_components(n: usize, edges: &[(usize, usize)]) -> Vec<usize> {
+        let mut label: Vec<usize> = (0..n).collect();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &(a, b) in edges {
+                let min = label[a].min(label[b]);
+                if label[a] != min || label[b] != min {
+                    label[a] = min;
+                    label[b] = min;
+                    changed = true;
+                }
+            }
+        }
+        label
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_naive_components(
+            n in 1usize..40,
+            edges in prop::collection::vec((0usize..40, 0usize..40), 0..60),
+        ) {
+            let edges: Vec<_> = edges.into_iter().map(|(a, b)| (a % n, b % n)).collect();
+            let mut sets = DisjointSet::new(n);
+            for &(a, b) in &edges {
+                sets.union(a, b);
+            }
+            let naive = naive_components(n, &edges);
+
+            for a in 0..n {
+                for b in 0..n {
+                    prop_assert_eq!(sets.same(a, b), naive[a] == naive[b]);
+                }
+                prop_assert_eq!(sets.size(a), naive.iter().filter(|&&l| l == naive[a]).count());
+            }
+
+            let mut distinct = naive.clone();
+            distinct.sort_unstable();
+            distinct.dedup();
+            prop_assert_eq!(sets.component_count(), distinct.len());
+            prop_assert_eq!(sets.component_sizes().iter().sum::<usize>(), n);
+        }
+    }
+}
@@ -0,0 +1,491 @@
+// Partial orders: "a must come before b" rules and topological sorting
+//
+// `OrderRules` holds rules between arbitrary hashable nodes.
+// `PrecedenceMatrix` holds the same kind of rules for small dense IDs (page
+// numbers, letters) as a bit matrix, so checking a rule is one bit lookup.
+// Both sort with Kahn's algorithm, which reports a cycle instead of
+// returning an arbitrary order when the rules contradict each other.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fmt;
+use std::hash::{BuildHasher, Hash, RandomState};
+
+/// The rules can't be satisfied: each node must come before the next, and
+/// the last before the first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError<N> {
+    pub cycle: Vec<N>,
+}
+
+impl<N: fmt::Debug> fmt::Display for CycleError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ordering rules form a cycle: ")?;
+        for node in &self.cycle {
+            write!(f, "{:?} -> ", node)?;
+        }
+        match self.cycle.first() {
+            Some(first) => write!(f, "{:?}", first),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<N: fmt::Debug> std::error::Error for CycleError<N> {}
+
+/// Kahn's algorithm over nodes `0..successors.len()`
+///
+/// The lowest ready index always goes next, so the result doesn't depend on
+/// the order of the edges.
+/// On failure returns one cycle among the nodes that could not be placed,
+/// starting from its lowest index.
+fn kahn(successors: &[Vec<usize>]) -> Result<Vec<usize>, Vec<usize>> {
+    let n = successors.len();
+    let mut in_degree = vec![0; n];
+    for &next in successors.iter().flatten() {
+        in_degree[next] += 1;
+    }
+
+    let mut ready: BinaryHeap<Reverse<usize>> =
+        (0..n).filter(|&i| in_degree[i] == 0).map(Reverse).collect();
+    let mut order = Vec::with_capacity(n);
+    while let Some(Reverse(node)) = ready.pop() {
+        order.push(node);
+        for &next in &successors[node] {
+            in_degree[next] -= 1;
+            if in_degree[next] == 0 {
+                ready.push(Reverse(next));
+            }
+        }
+    }
+
+    if order.len() == n {
+        return Ok(order);
+    }
+
+    // Every node left over has a predecessor that is also left over, so
+    // walking backwards through them must eventually repeat
+    let mut predecessor = vec![None; n];
+    for (node, nexts) in successors.iter().enumerate() {
+        for &next in nexts {
+            if in_degree[node] > 0 && in_degree[next] > 0 {
+                predecessor[next] = Some(node);
+            }
+        }
+    }
+
+    let mut seen_at = HashMap::new();
+    let mut walk = Vec::new();
+    let mut node = (0..n).find(|&i| in_degree[i] > 0).unwrap();
+    while !seen_at.contains_key(&node) {
+        seen_at.insert(node, walk.len());
+        walk.push(node);
+        node = predecessor[node].unwrap();
+    }
+
+    let mut cycle = walk.split_off(seen_at[&node]);
+    cycle.reverse();
+    let lowest = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap();
+    cycle.rotate_left(lowest);
+    Err(cycle)
+}
+
+/// Sort `nodes` so that `a` comes before `b` for every `(a, b)` edge
+///
+/// Nodes that only appear in edges are included too. Among nodes that are
+/// free to go next, the one listed first wins.
+///
+/// ```
+/// use aoc_common::graph::toposort;
+///
+/// let order = toposort(["shirt", "tie", "jacket"], [("tie", "jacket"), ("shirt", "tie")]);
+/// assert_eq!(order, Ok(vec!["shirt", "tie", "jacket"]));
+///
+/// let cycle = toposort([1, 2, 3], [(1, 2), (2, 3), (3, 1)]).unwrap_err();
+/// assert_eq!(cycle.cycle, [1, 2, 3]);
+/// ```
+pub fn toposort<N: Hash + Eq + Clone>(
+    nodes: impl IntoIterator<Item = N>,
+    edges: impl IntoIterator<Item = (N, N)>,
+) -> Result<Vec<N>, CycleError<N>> {
+    let mut index = HashMap::new();
+    let mut names = Vec::new();
+    let mut id = |node: N| {
+        *index.entry(node.clone()).or_insert_with(|| {
+            names.push(node);
+            names.len() - 1
+        })
+    };
+
+    for node in nodes {
+        id(node);
+    }
+    let edges: Vec<(usize, usize)> = edges.into_iter().map(|(a, b)| (id(a), id(b))).collect();
+
+    let mut successors = vec![Vec::new(); names.len()];
+    for (a, b) in edges {
+        successors[a].push(b);
+    }
+
+    let name = |i: usize| names[i].clone();
+    kahn(&successors)
+        .map(|order| order.into_iter().map(name).collect())
+        .map_err(|cycle| CycleError {
+            cycle: cycle.into_iter().map(name).collect(),
+        })
+}
+
+/// A set of "`a` comes before `b`" rules between arbitrary nodes
+///
+/// `S` is the hasher of the sets behind it, SipHash unless chosen otherwise.
+#[derive(Debug, Clone)]
+pub struct OrderRules<N, S = RandomState> {
+    after: HashMap<N, HashSet<N, S>, S>,
+}
+
+impl<N, S: Default> Default for OrderRules<N, S> {
+    fn default() -> Self {
+        Self {
+            after: HashMap::default(),
+        }
+    }
+}
+
+impl<N: Hash + Eq + Clone> OrderRules<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<N: Hash + Eq + Clone, S: BuildHasher + Default> OrderRules<N, S> {
+    /// Require `before` to come before `after`
+    pub fn insert(&mut self, before: N, after: N) {
+        self.after.entry(before).or_default().insert(after);
+    }
+
+    /// Whether there is a direct rule putting `a` before `b`
+    pub fn precedes(&self, a: &N, b: &N) -> bool {
+        self.after.get(a).is_some_and(|after| after.contains(b))
+    }
+
+    /// `Less` if a rule puts `a` first, `Greater` if one puts `b` first, and
+    /// `Equal` if no rule relates them
+    pub fn compare(&self, a: &N, b: &N) -> Ordering {
+        if self.precedes(a, b) {
+            Ordering::Less
+        } else if self.precedes(b, a) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+
+    /// All rules as `(before, after)` pairs
+    pub fn iter(&self) -> impl Iterator<Item = (&N, &N)> {
+        self.after
+            .iter()
+            .flat_map(|(before, after)| after.iter().map(move |a| (before, a)))
+    }
+
+    /// Only the rules between members of `nodes`
+    pub fn restrict(&self, nodes: &[N]) -> Self {
+        let members: HashSet<&N, S> = nodes.iter().collect();
+        let mut restricted = Self::default();
+        for (before, after) in self.iter() {
+            if members.contains(before) && members.contains(after) {
+                restricted.insert(before.clone(), after.clone());
+            }
+        }
+        restricted
+    }
+
+    /// Whether `order` breaks none of the rules between its members
+    pub fn is_consistent_order(&self, order: &[N]) -> bool {
+        let position: HashMap<&N, usize, S> =
+            order.iter().enumerate().map(|(i, n)| (n, i)).collect();
+        order.iter().enumerate().all(|(i, node)| {
+            self.after.get(node).is_none_or(|after| {
+                after
+                    .iter()
+                    .all(|next| position.get(next).is_none_or(|&j| i < j))
+            })
+        })
+    }
+
+    /// Sort `nodes` according to the rules between them
+    pub fn toposort(&self, nodes: &[N]) -> Result<Vec<N>, CycleError<N>> {
+        let restricted = self.restrict(nodes);
+        let edges = restricted.iter().map(|(a, b)| (a.clone(), b.clone()));
+        toposort(nodes.iter().cloned(), edges)
+    }
+}
+
+impl<N: Hash + Eq + Clone, S: BuildHasher + Default> FromIterator<(N, N)> for OrderRules<N, S> {
+    fn from_iter<I: IntoIterator<Item = (N, N)>>(iter: I) -> Self {
+        let mut rules = Self::default();
+        for (before, after) in iter {
+            rules.insert(before, after);
+        }
+        rules
+    }
+}
+
+/// "`a` comes before `b`" rules between IDs `0..size`, stored as a bit matrix
+///
+/// ```
+/// use aoc_common::graph::PrecedenceMatrix;
+///
+/// let rules: PrecedenceMatrix = [(47, 53), (97, 13), (97, 47), (47, 13)].into_iter().collect();
+/// assert!(rules.is_consistent_order(&[97, 47, 53]));
+/// assert!(!rules.is_consistent_order(&[47, 97]));
+/// assert_eq!(rules.toposort(&[13, 47, 97]), Ok(vec![97, 47, 13]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrecedenceMatrix {
+    size: usize,
+    words_per_row: usize,
+    bits: Vec<u64>,
+}
+
+impl PrecedenceMatrix {
+    /// An empty matrix for IDs below `size`
+    pub fn new(size: usize) -> Self {
+        let words_per_row = size.div_ceil(64);
+        Self {
+            size,
+            words_per_row,
+            bits: vec![0; size * words_per_row],
+        }
+    }
+
+    /// One more than the largest ID the matrix can hold
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    fn bit(&self, a: usize, b: usize) -> Option<(usize, u64)> {
+        (a < self.size && b < self.size).then(|| (a * self.words_per_row + b / 64, 1 << (b % 64)))
+    }
+
+    /// Require `before` to come before `after`
+    ///
+    /// Panics if either ID is not below `size`.
+    pub fn insert(&mut self, before: usize, after: usize) {
+        let (word, mask) = self.bit(before, after).unwrap_or_else(|| {
+            panic!(
+                "ID out of range for a PrecedenceMatrix of size {}",
+                self.size
+            )
+        });
+        self.bits[word] |= mask;
+    }
+
+    /// Whether there is a rule putting `a` before `b`; false for any ID at or
+    /// beyond `size`, since no rule mentions it
+    pub fn precedes(&self, a: usize, b: usize) -> bool {
+        self.bit(a, b)
+            .is_some_and(|(word, mask)| self.bits[word] & mask != 0)
+    }
+
+    /// `Less` if a rule puts `a` first, `Greater` if one puts `b` first, and
+    /// `Equal` if no rule relates them
+    pub fn compare(&self, a: usize, b: usize) -> Ordering {
+        if self.precedes(a, b) {
+            Ordering::Less
+        } else if self.precedes(b, a) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+
+    /// Whether `order` breaks none of the rules between its members
+    ///
+    /// A rule putting a node before itself can never be kept.
+    pub fn is_consistent_order(&self, order: &[usize]) -> bool {
+        order
+            .iter()
+            .enumerate()
+            .all(|(i, &a)| order[i..].iter().all(|&b| !self.precedes(b, a)))
+    }
+
+    /// Sort `nodes` according to the rules between them
+    ///
+    /// Unlike sorting with `compare`, this is correct even when the rules
+    /// only relate some pairs and the order has to follow chains of them.
+    pub fn toposort(&self, nodes: &[usize]) -> Result<Vec<usize>, CycleError<usize>> {
+        let successors: Vec<Vec<usize>> = nodes
+            .iter()
+            .map(|&a| {
+                (0..nodes.len())
+                    .filter(|&j| self.precedes(a, nodes[j]))
+                    .collect()
+            })
+            .collect();
+
+        kahn(&successors)
+            .map(|order| order.into_iter().map(|i| nodes[i]).collect())
+            .map_err(|cycle| CycleError {
+                cycle: cycle.into_iter().map(|i| nodes[i]).collect(),
+            })
+    }
+}
+
+impl FromIterator<(usize, usize)> for PrecedenceMatrix {
+    /// Collect rules, sizing the matrix to fit the largest ID
+    fn from_iter<I: IntoIterator<Item = (usize, usize)>>(iter: I) -> Self {
+        let rules: Vec<(usize, usize)> = iter.into_iter().collect();
+        let size = rules.iter().map(|&(a, b)| a.max(b) + 1).max().unwrap_or(0);
+
+        let mut matrix = Self::new(size);
+        for (before, after) in rules {
+            matrix.insert(before, after);
+        }
+        matrix
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toposort_includes_edge_only_nodes() {
+        let order = toposort(["b"], [("a", "b"), ("b", "c")]).unwrap();
+        assert_eq!(order, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_toposort_reports_cycle() {
+        // 0 is fine, then 1 -> 2 -> 3 -> 1 with 4 hanging off the cycle
+        let err = toposort(0..5, [(0, 1), (1, 2), (2, 3), (3, 1), (3, 4)]).unwrap_err();
+        assert_eq!(err.cycle.len(), 3);
+        for (i, a) in err.cycle.iter().enumerate() {
+            let b = err.cycle[(i + 1) % 3];
+            assert!([(1, 2), (2, 3), (3, 1)].contains(&(*a, b)));
+        }
+        assert!(err.to_string().starts_with("ordering rules form a cycle"));
+    }
+
+    #[test]
+    fn test_toposort_self_loop() {
+        assert_eq!(toposort([7], [(7, 7)]).unwrap_err().cycle, [7]);
+    }
+
+    #[test]
+    fn test_order_rules() {
+        let rules: OrderRules<&str> = [("a", "b"), ("b", "c"), ("x", "c")].into_iter().collect();
+
+        assert!(rules.precedes(&"a", &"b"));
+        assert_eq!(rules.compare(&"c", &"b"), Ordering::Greater);
+        assert_eq!(rules.compare(&"a", &"c"), Ordering::Equal);
+
+        assert!(rules.is_consistent_order(&["a", "b", "c"]));
+        assert!(rules.is_consistent_order(&["a", "c"]));
+        assert!(!rules.is_consistent_order(&["c", "x"]));
+
+        let restricted = rules.restrict(&["a", "b"]);
+        assert_eq!(restricted.iter().count(), 1);
+
+        // Sorting by `compare` can't see that a < c through b
+        assert_eq!(rules.toposort(&["c", "b", "a"]), Ok(vec!["a", "b", "c"]));
+    }
+
+    #[test]
+    fn test_precedence_matrix() {
+        let mut matrix = PrecedenceMatrix::new(130);
+        matrix.insert(3, 129);
+        matrix.insert(129, 64);
+
+        assert!(matrix.precedes(3, 129));
+        assert!(!matrix.precedes(129, 3));
+        assert_eq!(matrix.compare(64, 129), Ordering::Greater);
+        assert!(matrix.is_consistent_order(&[3, 129, 64]));
+        assert!(!matrix.is_consistent_order(&[64, 3, 129]));
+        assert_eq!(matrix.toposort(&[64, 129, 3]), Ok(vec![3, 129, 64]));
+
+        matrix.insert(64, 3);
+        assert!(matrix.toposort(&[64, 129, 3]).is_err());
+    }
+
+    #[test]
+    fn test_precedence_matrix_ids_beyond_size() {
+        let matrix: PrecedenceMatrix = [(1, 2)].into_iter().collect();
+        assert_eq!(matrix.size(), 3);
+        assert!(!matrix.precedes(1, 500));
+        assert_eq!(matrix.compare(500, 2), Ordering::Equal);
+        assert!(matrix.is_consistent_order(&[1, 500, 2]));
+        assert_eq!(matrix.toposort(&[500, 2, 1]), Ok(vec![500, 1, 2]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_precedence_matrix_insert_beyond_size() {
+        PrecedenceMatrix::new(3).insert(1, 3);
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use super::*;
+    use crate::collections::FxBuildHasher;
+    use proptest::prelude::*;
+
+    fn edges() -> impl Strategy<Value = Vec<(usize, usize)>> {
+        prop::collection::vec((0usize..12, 0usize..12), 0..30)
+    }
+
+    /// Whether the edges have a cycle, by repeatedly removing sources
+    fn has_cycle(n: usize, edges: &[(usize, usize)]) -> bool {
+        let mut alive = vec![true; n];
+        loop {
+            let source =
+                (0..n).find(|&v| alive[v] && !edges.iter().any(|&(a, b)| b == v && alive[a]));
+            match source {
+                Some(v) => alive[v] = false,
+                None => return alive.contains(&true),
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_toposort_respects_edges_or_finds_cycle(edges in edges()) {
+            match toposort(0..12, edges.iter().copied()) {
+                Ok(order) => {
+                    prop_assert!(!has_cycle(12, &edges));
+                    prop_assert_eq!(order.len(), 12);
+                    let rules: OrderRules<usize> = edges.iter().copied().collect();
+                    prop_assert!(rules.is_consistent_order(&order));
+                }
+                Err(CycleError { cycle }) => {
+                    prop_assert!(has_cycle(12, &edges));
+                    for (i, &a) in cycle.iter().enumerate() {
+                        let b = cycle[(i + 1) % cycle.len()];
+                        prop_assert!(edges.contains(&(a, b)));
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn test_matrix_matches_rules(edges in edges(), order in Just((0..12).collect::<Vec<usize>>()).prop_shuffle()) {
+            let rules: OrderRules<usize> = edges.iter().copied().collect();
+            let mut matrix = PrecedenceMatrix::new(12);
+            for &(a, b) in &edges {
+                matrix.insert(a, b);
+            }
+
+            let subset = &order[..6];
+            prop_assert_eq!(matrix.is_consistent_order(subset), rules.is_consistent_order(subset));
+            prop_assert_eq!(matrix.toposort(subset).is_ok(), rules.toposort(subset).is_ok());
+            if let Ok(sorted) = matrix.toposort(subset) {
+                prop_assert!(rules.is_consistent_order(&sorted));
+            }
+
+            let fx: OrderRules<usize, FxBuildHasher> = edges.iter().copied().collect();
+            prop_assert_eq!(fx.is_consistent_order(subset), rules.is_consistent_order(subset));
+            prop_assert_eq!(fx.toposort(subset), rules.toposort(subset));
+        }
+    }
+}
@@ -1,3 +1,17 @@
+mod bits;
+mod geometry;
+mod render;
+mod sparse;
+mod transform;
+mod view;
+
+pub use bits::BitGrid;
+pub use geometry::{Position3, Vector, Vector3};
+pub use render::{Color, Render};
+pub use sparse::SparseGrid;
+pub use transform::Symmetry;
+pub use view::{GridView, Line};
+
 // New Position type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Position {
@@ -19,6 +33,27 @@ impl Position {
         }
     }
 
+    // Move in one of eight directions
+    pub fn step8(&self, direction: Direction8) -> Self {
+        let (dx, dy) = direction.delta();
+        Self {
+            x: self.x + dx,
+            y: self.y + dy,
+        }
+    }
+
+    // Orthogonal neighbours, in `Direction::ALL` order
+    pub fn neighbors4(&self) -> impl Iterator<Item = Position> + use<> {
+        let pos = *self;
+        Direction::ALL.into_iter().map(move |d| pos.step(d))
+    }
+
+    // Orthogonal and diagonal neighbours, in `Direction8::ALL` order
+    pub fn neighbors8(&self) -> impl Iterator<Item = Position> + use<> {
+        let pos = *self;
+        Direction8::ALL.into_iter().map(move |d| pos.step8(d))
+    }
+
     // Get row/col indices (matching array indexing order [y][x])
     pub fn row_col(&self) -> Option<(usize, usize)> {
         if self.x < 0 || self.y < 0 {
@@ -79,6 +114,222 @@ impl Direction {
             Direction::WEST => Direction::NORTH,
         }
     }
+
+    pub fn turn_left(&self) -> Direction {
+        match self {
+            Direction::NORTH => Direction::WEST,
+            Direction::EAST => Direction::NORTH,
+            Direction::SOUTH => Direction::EAST,
+            Direction::WEST => Direction::SOUTH,
+        }
+    }
+
+    pub fn reverse(&self) -> Direction {
+        self.rotate(2)
+    }
+
+    // Turn clockwise by a number of quarter turns (negative turns counter-clockwise)
+    pub fn rotate(&self, quarter_turns: i32) -> Direction {
+        Direction::ALL[(self.index() as i32 + quarter_turns).rem_euclid(4) as usize]
+    }
+
+    // Turn clockwise by a multiple of 90 degrees
+    pub fn rotate_degrees(&self, degrees: i32) -> Option<Direction> {
+        (degrees % 90 == 0).then(|| self.rotate(degrees / 90))
+    }
+
+    // Parse `^>v<`, `URDL` or `NESW`
+    pub fn from_char(ch: char) -> Option<Direction> {
+        match ch {
+            '^' | 'U' | 'N' => Some(Direction::NORTH),
+            '>' | 'R' | 'E' => Some(Direction::EAST),
+            'v' | 'D' | 'S' => Some(Direction::SOUTH),
+            '<' | 'L' | 'W' => Some(Direction::WEST),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub enum Direction8 {
+    NORTH,
+    NORTHEAST,
+    EAST,
+    SOUTHEAST,
+    SOUTH,
+    SOUTHWEST,
+    WEST,
+    NORTHWEST,
+}
+
+impl Direction8 {
+    // Type-safe index for use in arrays
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    // Clockwise, starting at north
+    pub const ALL: [Direction8; 8] = [
+        Direction8::NORTH,
+        Direction8::NORTHEAST,
+        Direction8::EAST,
+        Direction8::SOUTHEAST,
+        Direction8::SOUTH,
+        Direction8::SOUTHWEST,
+        Direction8::WEST,
+        Direction8::NORTHWEST,
+    ];
+
+    pub fn delta(&self) -> (i64, i64) {
+        match self {
+            Direction8::NORTH => (0, -1),
+            Direction8::NORTHEAST => (1, -1),
+            Direction8::EAST => (1, 0),
+            Direction8::SOUTHEAST => (1, 1),
+            Direction8::SOUTH => (0, 1),
+            Direction8::SOUTHWEST => (-1, 1),
+            Direction8::WEST => (-1, 0),
+            Direction8::NORTHWEST => (-1, -1),
+        }
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    // Turn 45 degrees clockwise
+    pub fn turn_right(&self) -> Direction8 {
+        self.rotate(1)
+    }
+
+    // Turn 45 degrees counter-clockwise
+    pub fn turn_left(&self) -> Direction8 {
+        self.rotate(-1)
+    }
+
+    pub fn reverse(&self) -> Direction8 {
+        self.rotate(4)
+    }
+
+    // Turn clockwise by a number of 45 degree steps (negative turns counter-clockwise)
+    pub fn rotate(&self, eighth_turns: i32) -> Direction8 {
+        Direction8::ALL[(self.index() as i32 + eighth_turns).rem_euclid(8) as usize]
+    }
+
+    // Turn clockwise by a multiple of 45 degrees
+    pub fn rotate_degrees(&self, degrees: i32) -> Option<Direction8> {
+        (degrees % 45 == 0).then(|| self.rotate(degrees / 45))
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        Direction8::ALL[direction.index() * 2]
+    }
+}
+
+// Hex grid position in axial coordinates (flat-topped hexes, `r` grows southward)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HexPosition {
+    pub q: i64,
+    pub r: i64,
+}
+
+impl HexPosition {
+    pub fn new(q: i64, r: i64) -> Self {
+        Self { q, r }
+    }
+
+    pub fn step(&self, direction: HexDirection) -> Self {
+        let (dq, dr) = direction.delta();
+        Self {
+            q: self.q + dq,
+            r: self.r + dr,
+        }
+    }
+
+    // All six neighbours, in `HexDirection::ALL` order
+    pub fn neighbors(&self) -> impl Iterator<Item = HexPosition> + use<> {
+        let pos = *self;
+        HexDirection::ALL.into_iter().map(move |d| pos.step(d))
+    }
+
+    // Number of steps between two hexes
+    pub fn distance(&self, other: HexPosition) -> i64 {
+        let (dq, dr) = (self.q - other.q, self.r - other.r);
+        (dq.abs() + dr.abs() + (dq + dr).abs()) / 2
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub enum HexDirection {
+    NORTH,
+    NORTHEAST,
+    SOUTHEAST,
+    SOUTH,
+    SOUTHWEST,
+    NORTHWEST,
+}
+
+impl HexDirection {
+    // Type-safe index for use in arrays
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    // Clockwise, starting at north
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::NORTH,
+        HexDirection::NORTHEAST,
+        HexDirection::SOUTHEAST,
+        HexDirection::SOUTH,
+        HexDirection::SOUTHWEST,
+        HexDirection::NORTHWEST,
+    ];
+
+    // Axial (dq, dr) offset
+    pub fn delta(&self) -> (i64, i64) {
+        match self {
+            HexDirection::NORTH => (0, -1),
+            HexDirection::NORTHEAST => (1, -1),
+            HexDirection::SOUTHEAST => (1, 0),
+            HexDirection::SOUTH => (0, 1),
+            HexDirection::SOUTHWEST => (-1, 1),
+            HexDirection::NORTHWEST => (-1, 0),
+        }
+    }
+
+    // Turn 60 degrees clockwise
+    pub fn turn_right(&self) -> HexDirection {
+        self.rotate(1)
+    }
+
+    // Turn 60 degrees counter-clockwise
+    pub fn turn_left(&self) -> HexDirection {
+        self.rotate(-1)
+    }
+
+    pub fn reverse(&self) -> HexDirection {
+        self.rotate(3)
+    }
+
+    // Turn clockwise by a number of 60 degree steps (negative turns counter-clockwise)
+    pub fn rotate(&self, sixth_turns: i32) -> HexDirection {
+        HexDirection::ALL[(self.index() as i32 + sixth_turns).rem_euclid(6) as usize]
+    }
+
+    // Parse `n`, `ne`, `se`, `s`, `sw` or `nw`
+    pub fn from_name(name: &str) -> Option<HexDirection> {
+        match name {
+            "n" => Some(HexDirection::NORTH),
+            "ne" => Some(HexDirection::NORTHEAST),
+            "se" => Some(HexDirection::SOUTHEAST),
+            "s" => Some(HexDirection::SOUTH),
+            "sw" => Some(HexDirection::SOUTHWEST),
+            "nw" => Some(HexDirection::NORTHWEST),
+            _ => None,
+        }
+    }
 }
 
 // Update cell_at to work with Position
@@ -86,3 +337,364 @@ pub fn cell_at(grid: &[Vec<char>], pos: Position) -> Option<char> {
     let (row, col) = pos.row_col()?;
     grid.get(row)?.get(col).copied()
 }
+
+/// Row-major grid with `width` columns and `height` rows
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    /// A row whose length differs from the first row
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// `from_vec` with a cell count that isn't `width * height`
+    WrongSize { expected: usize, found: usize },
+}
+
+impl std::fmt::Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(f, "row {} has {} cells, expected {}", row, found, expected),
+            GridError::WrongSize { expected, found } => {
+                write!(f, "expected {} cells, found {}", expected, found)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+impl<T> Grid<T> {
+    /// Create a grid filled with `value`
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Wrap row-major cells
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Result<Self, GridError> {
+        if cells.len() != width * height {
+            return Err(GridError::WrongSize {
+                expected: width * height,
+                found: cells.len(),
+            });
+        }
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Parse one cell per character, one row per line
+    pub fn from_str_with(input: &str, mut f: impl FnMut(char) -> T) -> Result<Self, GridError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(input.len());
+
+        for (row, line) in input.lines().enumerate() {
+            let before = cells.len();
+            cells.extend(line.chars().map(&mut f));
+            let found = cells.len() - before;
+
+            let expected = *width.get_or_insert(found);
+            if found != expected {
+                return Err(GridError::RaggedRow {
+                    row,
+                    expected,
+                    found,
+                });
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `pos` lies inside the grid
+    pub fn contains(&self, pos: Position) -> bool {
+        pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.width && (pos.y as usize) < self.height
+    }
+
+    fn index_of(&self, pos: Position) -> Option<usize> {
+        self.contains(pos)
+            .then(|| pos.y as usize * self.width + pos.x as usize)
+    }
+
+    fn position_of(&self, index: usize) -> Position {
+        Position::from((index % self.width, index / self.width))
+    }
+
+    pub fn get(&self, pos: Position) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Position) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Position of the first cell equal to `value` (row by row)
+    pub fn find(&self, value: &T) -> Option<Position>
+    where
+        T: PartialEq,
+    {
+        self.cells
+            .iter()
+            .position(|cell| cell == value)
+            .map(|i| self.position_of(i))
+    }
+
+    /// All positions, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Position> + use<T> {
+        let (width, height) = (self.width, self.height);
+        (0..height).flat_map(move |y| (0..width).map(move |x| Position::from((x, y))))
+    }
+
+    /// Cells with their positions, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Position, &mut T)> {
+        self.positions().zip(self.cells.iter_mut())
+    }
+
+    /// Rows as slices
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Convert every cell to a different type
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Set every cell to `value`, reusing the allocation
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.cells.fill(value);
+    }
+}
+
+impl std::str::FromStr for Grid<char> {
+    type Err = GridError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::from_str_with(input, |ch| ch)
+    }
+}
+
+impl<T> std::ops::Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Position) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("position {:?} is outside the grid", pos))
+    }
+}
+
+impl<T> std::ops::IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, pos: Position) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {:?} is outside the grid", pos))
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "..#\n.^.\n#..\n";
+
+    #[test]
+    fn test_parse_and_display() {
+        let grid: Grid<char> = EXAMPLE.parse().unwrap();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid.to_string(), EXAMPLE.trim_end());
+    }
+
+    #[test]
+    fn test_ragged_rows_are_rejected() {
+        assert_eq!(
+            "...\n..\n".parse::<Grid<char>>(),
+            Err(GridError::RaggedRow {
+                row: 1,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert!(Grid::from_vec(2, 2, vec![1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn test_get_and_index() {
+        let mut grid: Grid<char> = EXAMPLE.parse().unwrap();
+        assert_eq!(grid.get(Position::new(2, 0)), Some(&'#'));
+        assert_eq!(grid.get(Position::new(-1, 0)), None);
+        assert_eq!(grid.get(Position::new(3, 0)), None);
+
+        grid[Position::new(0, 0)] = '@';
+        assert_eq!(grid[Position::new(0, 0)], '@');
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_bounds_panics() {
+        let grid: Grid<char> = EXAMPLE.parse().unwrap();
+        let _ = grid[Position::new(0, 3)];
+    }
+
+    #[test]
+    fn test_find_and_iter() {
+        let grid: Grid<char> = EXAMPLE.parse().unwrap();
+        assert_eq!(grid.find(&'^'), Some(Position::new(1, 1)));
+        assert_eq!(grid.find(&'X'), None);
+
+        let walls: Vec<Position> = grid
+            .iter()
+            .filter(|&(_, &ch)| ch == '#')
+            .map(|(pos, _)| pos)
+            .collect();
+        assert_eq!(walls, vec![Position::new(2, 0), Position::new(0, 2)]);
+    }
+
+    #[test]
+    fn test_direction_turns() {
+        for d in Direction::ALL {
+            assert_eq!(d.turn_right().turn_left(), d);
+            assert_eq!(d.reverse().reverse(), d);
+            assert_eq!(d.rotate(1), d.turn_right());
+            assert_eq!(d.rotate(-1), d.turn_left());
+            assert_eq!(d.rotate(-7), d.turn_right());
+        }
+        assert_eq!(Direction::NORTH.reverse(), Direction::SOUTH);
+        assert_eq!(Direction::EAST.rotate_degrees(-90), Some(Direction::NORTH));
+        assert_eq!(Direction::EAST.rotate_degrees(45), None);
+    }
+
+    #[test]
+    fn test_direction_from_char() {
+        let parsed: Vec<_> = "^>v<".chars().filter_map(Direction::from_char).collect();
+        assert_eq!(parsed, Direction::ALL);
+        assert_eq!(Direction::from_char('U'), Some(Direction::NORTH));
+        assert_eq!(Direction::from_char('L'), Some(Direction::WEST));
+        assert_eq!(Direction::from_char('S'), Some(Direction::SOUTH));
+        assert_eq!(Direction::from_char('x'), None);
+    }
+
+    #[test]
+    fn test_direction8() {
+        assert_eq!(Direction8::NORTH.turn_right(), Direction8::NORTHEAST);
+        assert_eq!(Direction8::NORTH.turn_left(), Direction8::NORTHWEST);
+        assert_eq!(Direction8::NORTHEAST.reverse(), Direction8::SOUTHWEST);
+        assert_eq!(
+            Direction8::EAST.rotate_degrees(135),
+            Some(Direction8::SOUTHWEST)
+        );
+        assert!(Direction8::SOUTHEAST.is_diagonal());
+
+        for d in Direction::ALL {
+            assert_eq!(Direction8::from(d).delta(), d.delta());
+        }
+        for d in Direction8::ALL {
+            let (dx, dy) = d.delta();
+            let (rx, ry) = d.reverse().delta();
+            assert_eq!((dx + rx, dy + ry), (0, 0));
+        }
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let pos = Position::new(0, 0);
+        let n4: Vec<_> = pos.neighbors4().collect();
+        assert_eq!(
+            n4,
+            vec![
+                Position::new(0, -1),
+                Position::new(1, 0),
+                Position::new(0, 1),
+                Position::new(-1, 0)
+            ]
+        );
+        assert_eq!(pos.neighbors8().count(), 8);
+        assert!(pos.neighbors8().all(|n| n != pos));
+    }
+
+    #[test]
+    fn test_hex_directions() {
+        let origin = HexPosition::new(0, 0);
+        for d in HexDirection::ALL {
+            assert_eq!(origin.step(d).step(d.reverse()), origin);
+            assert_eq!(origin.step(d).distance(origin), 1);
+            assert_eq!(d.rotate(6), d);
+        }
+
+        // "ne,ne,s,s" ends two steps away
+        let end = ["ne", "ne", "s", "s"]
+            .iter()
+            .filter_map(|name| HexDirection::from_name(name))
+            .fold(origin, |pos, d| pos.step(d));
+        assert_eq!(end.distance(origin), 2);
+        assert_eq!(origin.neighbors().count(), 6);
+    }
+
+    #[test]
+    fn test_map_and_fill() {
+        let grid = Grid::from_str_with(EXAMPLE, |ch| ch == '#').unwrap();
+        let mut counts = grid.map(|&wall| wall as u8);
+        assert_eq!(counts.to_string(), "001\n000\n100");
+
+        counts.fill(7);
+        assert!(counts.iter().all(|(_, &n)| n == 7));
+    }
+}
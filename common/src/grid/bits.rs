@@ -0,0 +1,199 @@
+// One bit per cell (and per plane) for visited sets on a fixed-size grid
+//
+// Planes give each cell several independent flags, typically one per
+// `Direction` via `Direction::index()`, so "been here facing this way" is a
+// single bit test. `clear` only zeroes the words that were written since the
+// last clear, so simulations that reset state for every candidate pay for
+// what they touched rather than for the whole grid.
+
+use super::{Grid, Position};
+
+/// Fixed-size grid of bits with `planes` flags per cell
+///
+/// ```
+/// use aoc_common::grid::{BitGrid, Direction, Position};
+///
+/// let mut seen = BitGrid::with_planes(10, 10, 4);
+/// let pos = Position::new(3, 4);
+/// assert!(seen.insert_in(pos, Direction::EAST.index()));
+/// assert!(!seen.insert_in(pos, Direction::EAST.index()));
+/// assert!(!seen.contains_in(pos, Direction::NORTH.index()));
+/// assert!(seen.contains(pos));
+/// ```
+#[derive(Debug, Clone)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    planes: usize,
+    words: Vec<u64>,
+    /// Words that became non-zero since the last clear
+    touched: Vec<usize>,
+}
+
+impl BitGrid {
+    /// Empty grid with one plane
+    pub fn new(width: usize, height: usize) -> Self {
+        Self::with_planes(width, height, 1)
+    }
+
+    /// Empty grid with `planes` flags per cell
+    pub fn with_planes(width: usize, height: usize, planes: usize) -> Self {
+        assert!(planes > 0, "a bit grid needs at least one plane");
+        Self {
+            width,
+            height,
+            planes,
+            words: vec![0; (width * height * planes).div_ceil(64)],
+            touched: Vec::new(),
+        }
+    }
+
+    /// Empty grid the same size as `grid`
+    pub fn like<T>(grid: &Grid<T>, planes: usize) -> Self {
+        Self::with_planes(grid.width(), grid.height(), planes)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn planes(&self) -> usize {
+        self.planes
+    }
+
+    fn bit(&self, pos: Position, plane: usize) -> Option<usize> {
+        let inside = pos.x >= 0
+            && pos.y >= 0
+            && (pos.x as usize) < self.width
+            && (pos.y as usize) < self.height
+            && plane < self.planes;
+        inside.then(|| (pos.y as usize * self.width + pos.x as usize) * self.planes + plane)
+    }
+
+    /// Set the flag for `plane` at `pos`, returning whether it was newly set
+    ///
+    /// Panics if `pos` or `plane` is out of range.
+    pub fn insert_in(&mut self, pos: Position, plane: usize) -> bool {
+        let bit = self
+            .bit(pos, plane)
+            .unwrap_or_else(|| panic!("{:?} plane {} is outside the bit grid", pos, plane));
+        let (word, mask) = (bit / 64, 1 << (bit % 64));
+        if self.words[word] & mask != 0 {
+            return false;
+        }
+        if self.words[word] == 0 {
+            self.touched.push(word);
+        }
+        self.words[word] |= mask;
+        true
+    }
+
+    /// Set the flag at `pos` in the first plane
+    pub fn insert(&mut self, pos: Position) -> bool {
+        self.insert_in(pos, 0)
+    }
+
+    /// Whether the flag for `plane` is set at `pos`; false outside the grid
+    pub fn contains_in(&self, pos: Position, plane: usize) -> bool {
+        self.bit(pos, plane)
+            .is_some_and(|bit| self.words[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Whether any plane is set at `pos`
+    pub fn contains(&self, pos: Position) -> bool {
+        (0..self.planes).any(|plane| self.contains_in(pos, plane))
+    }
+
+    /// Number of set flags across all planes
+    pub fn count(&self) -> usize {
+        self.touched
+            .iter()
+            .map(|&word| self.words[word].count_ones() as usize)
+            .sum()
+    }
+
+    /// Positions with at least one plane set, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Position> + '_ {
+        (0..self.height)
+            .flat_map(move |y| (0..self.width).map(move |x| Position::from((x, y))))
+            .filter(|&pos| self.contains(pos))
+    }
+
+    /// Unset everything, in time proportional to what was set
+    pub fn clear(&mut self) {
+        if self.touched.len() * 4 > self.words.len() {
+            self.words.fill(0);
+        } else {
+            for &word in &self.touched {
+                self.words[word] = 0;
+            }
+        }
+        self.touched.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Direction;
+
+    #[test]
+    fn test_planes_are_independent() {
+        let mut bits = BitGrid::with_planes(5, 3, 4);
+        let pos = Position::new(4, 2);
+        for d in Direction::ALL {
+            assert!(!bits.contains_in(pos, d.index()));
+            assert!(bits.insert_in(pos, d.index()));
+        }
+        assert_eq!(bits.count(), 4);
+        assert!(!bits.contains(Position::new(3, 2)));
+        assert!(!bits.contains(Position::new(5, 2)));
+        assert!(!bits.contains_in(pos, 4));
+    }
+
+    #[test]
+    fn test_clear_only_touches_written_words() {
+        let mut bits = BitGrid::new(100, 100);
+        bits.insert(Position::new(0, 0));
+        bits.insert(Position::new(1, 0));
+        bits.insert(Position::new(99, 99));
+        assert_eq!(bits.touched.len(), 2);
+        assert_eq!(
+            bits.positions().collect::<Vec<_>>(),
+            [
+                Position::new(0, 0),
+                Position::new(1, 0),
+                Position::new(99, 99)
+            ]
+        );
+
+        bits.clear();
+        assert_eq!(bits.count(), 0);
+        assert!(bits.words.iter().all(|&w| w == 0));
+        assert!(bits.insert(Position::new(0, 0)));
+        assert_eq!(bits.count(), 1);
+    }
+
+    #[test]
+    fn test_clear_after_filling() {
+        let mut bits = BitGrid::new(8, 8);
+        for y in 0..8 {
+            for x in 0..8 {
+                bits.insert(Position::new(x, y));
+            }
+        }
+        assert_eq!(bits.count(), 64);
+        bits.clear();
+        assert_eq!(bits.positions().count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_insert_outside_panics() {
+        BitGrid::new(3, 3).insert(Position::new(-1, 0));
+    }
+}
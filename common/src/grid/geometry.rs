@@ -0,0 +1,460 @@
+// Arithmetic on positions and offsets, plus their 3-D counterparts
+//
+// A `Position` is a point and a `Vector` is an offset between points:
+// `Position + Vector = Position` and `Position - Position = Vector`.
+
+use std::cmp::Ordering;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use super::{Direction, Direction8, Grid, Position};
+
+/// Offset between two positions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Vector {
+    pub dx: i64,
+    pub dy: i64,
+}
+
+impl Vector {
+    pub const ZERO: Vector = Vector { dx: 0, dy: 0 };
+
+    pub const fn new(dx: i64, dy: i64) -> Self {
+        Self { dx, dy }
+    }
+
+    pub fn manhattan(&self) -> i64 {
+        self.dx.abs() + self.dy.abs()
+    }
+
+    pub fn chebyshev(&self) -> i64 {
+        self.dx.abs().max(self.dy.abs())
+    }
+
+    // Rotate 90 degrees clockwise (y grows downward)
+    pub fn rotate_right(&self) -> Self {
+        Self::new(-self.dy, self.dx)
+    }
+
+    // Rotate 90 degrees counter-clockwise (y grows downward)
+    pub fn rotate_left(&self) -> Self {
+        Self::new(self.dy, -self.dx)
+    }
+}
+
+impl From<Direction> for Vector {
+    fn from(direction: Direction) -> Self {
+        let (dx, dy) = direction.delta();
+        Self::new(dx, dy)
+    }
+}
+
+impl From<Direction8> for Vector {
+    fn from(direction: Direction8) -> Self {
+        let (dx, dy) = direction.delta();
+        Self::new(dx, dy)
+    }
+}
+
+impl From<(i64, i64)> for Vector {
+    fn from((dx, dy): (i64, i64)) -> Self {
+        Self::new(dx, dy)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, rhs: Vector) -> Vector {
+        Vector::new(self.dx + rhs.dx, self.dy + rhs.dy)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, rhs: Vector) -> Vector {
+        Vector::new(self.dx - rhs.dx, self.dy - rhs.dy)
+    }
+}
+
+impl Mul<i64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, rhs: i64) -> Vector {
+        Vector::new(self.dx * rhs, self.dy * rhs)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.dx, -self.dy)
+    }
+}
+
+impl Position {
+    pub const ORIGIN: Position = Position { x: 0, y: 0 };
+
+    pub fn manhattan(&self, other: Position) -> i64 {
+        (*self - other).manhattan()
+    }
+
+    pub fn chebyshev(&self, other: Position) -> i64 {
+        (*self - other).chebyshev()
+    }
+
+    // Rotate 90 degrees clockwise about the origin (y grows downward)
+    pub fn rotate_right(&self) -> Self {
+        Self::ORIGIN + (*self - Self::ORIGIN).rotate_right()
+    }
+
+    // Rotate 90 degrees counter-clockwise about the origin (y grows downward)
+    pub fn rotate_left(&self) -> Self {
+        Self::ORIGIN + (*self - Self::ORIGIN).rotate_left()
+    }
+
+    // Whether the position lies inside the grid
+    pub fn in_bounds<T>(&self, grid: &Grid<T>) -> bool {
+        grid.contains(*self)
+    }
+}
+
+// Reading order: top to bottom, then left to right
+impl Ord for Position {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.y, self.x).cmp(&(other.y, other.x))
+    }
+}
+
+impl PartialOrd for Position {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add<Vector> for Position {
+    type Output = Position;
+
+    fn add(self, rhs: Vector) -> Position {
+        Position::new(self.x + rhs.dx, self.y + rhs.dy)
+    }
+}
+
+impl Sub<Vector> for Position {
+    type Output = Position;
+
+    fn sub(self, rhs: Vector) -> Position {
+        Position::new(self.x - rhs.dx, self.y - rhs.dy)
+    }
+}
+
+impl Sub for Position {
+    type Output = Vector;
+
+    fn sub(self, rhs: Position) -> Vector {
+        Vector::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl AddAssign<Vector> for Position {
+    fn add_assign(&mut self, rhs: Vector) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign<Vector> for Position {
+    fn sub_assign(&mut self, rhs: Vector) {
+        *self = *self - rhs;
+    }
+}
+
+// Scale about the origin
+impl Mul<i64> for Position {
+    type Output = Position;
+
+    fn mul(self, rhs: i64) -> Position {
+        Position::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+// Mirror through the origin
+impl Neg for Position {
+    type Output = Position;
+
+    fn neg(self) -> Position {
+        Position::new(-self.x, -self.y)
+    }
+}
+
+/// Point in 3-D space
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Position3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+/// Offset between two 3-D points
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Vector3 {
+    pub dx: i64,
+    pub dy: i64,
+    pub dz: i64,
+}
+
+// Layer by layer, each layer in reading order like `Position`
+impl Ord for Position3 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.z, self.y, self.x).cmp(&(other.z, other.y, other.x))
+    }
+}
+
+impl PartialOrd for Position3 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Position3 {
+    pub const ORIGIN: Position3 = Position3 { x: 0, y: 0, z: 0 };
+
+    pub fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn manhattan(&self, other: Position3) -> i64 {
+        (*self - other).manhattan()
+    }
+
+    pub fn chebyshev(&self, other: Position3) -> i64 {
+        (*self - other).chebyshev()
+    }
+
+    // The six face-adjacent neighbours
+    pub fn neighbors6(&self) -> impl Iterator<Item = Position3> + use<> {
+        let pos = *self;
+        Vector3::UNITS.into_iter().map(move |v| pos + v)
+    }
+
+    // All 26 neighbours sharing a face, edge or corner
+    pub fn neighbors26(&self) -> impl Iterator<Item = Position3> + use<> {
+        let pos = *self;
+        (-1..=1)
+            .flat_map(|dx| (-1..=1).flat_map(move |dy| (-1..=1).map(move |dz| (dx, dy, dz))))
+            .filter(|&d| d != (0, 0, 0))
+            .map(move |(dx, dy, dz)| pos + Vector3::new(dx, dy, dz))
+    }
+}
+
+impl Vector3 {
+    pub const ZERO: Vector3 = Vector3::new(0, 0, 0);
+
+    // Unit steps along each axis, in both directions
+    pub const UNITS: [Vector3; 6] = [
+        Vector3::new(1, 0, 0),
+        Vector3::new(-1, 0, 0),
+        Vector3::new(0, 1, 0),
+        Vector3::new(0, -1, 0),
+        Vector3::new(0, 0, 1),
+        Vector3::new(0, 0, -1),
+    ];
+
+    pub const fn new(dx: i64, dy: i64, dz: i64) -> Self {
+        Self { dx, dy, dz }
+    }
+
+    pub fn manhattan(&self) -> i64 {
+        self.dx.abs() + self.dy.abs() + self.dz.abs()
+    }
+
+    pub fn chebyshev(&self) -> i64 {
+        self.dx.abs().max(self.dy.abs()).max(self.dz.abs())
+    }
+}
+
+impl From<(i64, i64, i64)> for Position3 {
+    fn from((x, y, z): (i64, i64, i64)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl Add<Vector3> for Position3 {
+    type Output = Position3;
+
+    fn add(self, rhs: Vector3) -> Position3 {
+        Position3::new(self.x + rhs.dx, self.y + rhs.dy, self.z + rhs.dz)
+    }
+}
+
+impl Sub<Vector3> for Position3 {
+    type Output = Position3;
+
+    fn sub(self, rhs: Vector3) -> Position3 {
+        Position3::new(self.x - rhs.dx, self.y - rhs.dy, self.z - rhs.dz)
+    }
+}
+
+impl Sub for Position3 {
+    type Output = Vector3;
+
+    fn sub(self, rhs: Position3) -> Vector3 {
+        Vector3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl AddAssign<Vector3> for Position3 {
+    fn add_assign(&mut self, rhs: Vector3) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign<Vector3> for Position3 {
+    fn sub_assign(&mut self, rhs: Vector3) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i64> for Position3 {
+    type Output = Position3;
+
+    fn mul(self, rhs: i64) -> Position3 {
+        Position3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl Neg for Position3 {
+    type Output = Position3;
+
+    fn neg(self) -> Position3 {
+        Position3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Add for Vector3 {
+    type Output = Vector3;
+
+    fn add(self, rhs: Vector3) -> Vector3 {
+        Vector3::new(self.dx + rhs.dx, self.dy + rhs.dy, self.dz + rhs.dz)
+    }
+}
+
+impl Sub for Vector3 {
+    type Output = Vector3;
+
+    fn sub(self, rhs: Vector3) -> Vector3 {
+        Vector3::new(self.dx - rhs.dx, self.dy - rhs.dy, self.dz - rhs.dz)
+    }
+}
+
+impl Mul<i64> for Vector3 {
+    type Output = Vector3;
+
+    fn mul(self, rhs: i64) -> Vector3 {
+        Vector3::new(self.dx * rhs, self.dy * rhs, self.dz * rhs)
+    }
+}
+
+impl Neg for Vector3 {
+    type Output = Vector3;
+
+    fn neg(self) -> Vector3 {
+        Vector3::new(-self.dx, -self.dy, -self.dz)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_position_vector_arithmetic() {
+        let a = Position::new(3, 4);
+        let b = Position::new(1, 7);
+
+        assert_eq!(a - b, Vector::new(2, -3));
+        assert_eq!(b + (a - b), a);
+        assert_eq!(a + Vector::from(Direction::NORTH), a.step(Direction::NORTH));
+        assert_eq!(a + Vector::from(Direction::EAST) * 3, Position::new(6, 4));
+        assert_eq!(-a, Position::new(-3, -4));
+        assert_eq!(a * 2, Position::new(6, 8));
+
+        let mut c = a;
+        c += Vector::new(1, 1);
+        c -= Vector::new(0, 2);
+        assert_eq!(c, Position::new(4, 3));
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Position::new(0, 0);
+        let b = Position::new(3, -4);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(b.manhattan(a), 7);
+    }
+
+    #[test]
+    fn test_rotation_matches_direction_turns() {
+        for d in Direction::ALL {
+            let v = Vector::from(d);
+            assert_eq!(v.rotate_right(), Vector::from(d.turn_right()));
+            assert_eq!(v.rotate_left(), Vector::from(d.turn_left()));
+        }
+
+        let p = Position::new(2, 1);
+        assert_eq!(p.rotate_right(), Position::new(-1, 2));
+        assert_eq!(p.rotate_right().rotate_left(), p);
+    }
+
+    #[test]
+    fn test_in_bounds() {
+        let grid = Grid::new(3, 2, '.');
+        assert!(Position::new(2, 1).in_bounds(&grid));
+        assert!(!Position::new(3, 1).in_bounds(&grid));
+        assert!(!Position::new(0, -1).in_bounds(&grid));
+    }
+
+    #[test]
+    fn test_reading_order() {
+        let set: BTreeSet<Position> = [(2, 0), (0, 1), (1, 0), (0, 0)]
+            .into_iter()
+            .map(|(x, y): (i64, i64)| Position::new(x, y))
+            .collect();
+        let ordered: Vec<_> = set.into_iter().map(|p| (p.x, p.y)).collect();
+        assert_eq!(ordered, vec![(0, 0), (1, 0), (2, 0), (0, 1)]);
+
+        let mut points = vec![
+            Position3::new(0, 0, 1),
+            Position3::new(1, 0, 0),
+            Position3::new(0, 1, 0),
+        ];
+        points.sort();
+        assert_eq!(
+            points,
+            [
+                Position3::new(1, 0, 0),
+                Position3::new(0, 1, 0),
+                Position3::new(0, 0, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_position3() {
+        let a = Position3::new(1, 2, 3);
+        let b = Position3::new(-1, 0, 7);
+
+        assert_eq!(a - b, Vector3::new(2, 2, -4));
+        assert_eq!(b + (a - b), a);
+        assert_eq!(a.manhattan(b), 8);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(-a * 2, Position3::new(-2, -4, -6));
+
+        assert_eq!(a.neighbors6().count(), 6);
+        assert!(a.neighbors6().all(|n| n.manhattan(a) == 1));
+        assert_eq!(a.neighbors26().count(), 26);
+        assert!(a.neighbors26().all(|n| n.chebyshev(a) == 1));
+    }
+}
@@ -0,0 +1,437 @@
+// Text rendering of grids for debugging and snapshot tests
+//
+// A `Render` is a character picture of a grid that remembers which puzzle
+// coordinates its cells came from. Overlays, colours and cropping are all
+// given in those coordinates, so cropping first and marking later (or the
+// other way round) lands on the same cells.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Range;
+
+use super::{Grid, Position, SparseGrid};
+
+/// ANSI foreground colour for highlighted cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    fn ansi_code(self) -> u8 {
+        match self {
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Cell {
+    ch: char,
+    color: Option<Color>,
+}
+
+/// One line of a side-by-side picture
+#[derive(Debug, Clone, Copy)]
+enum Slot {
+    /// The i-th line of the shared ruler header
+    Header(usize),
+    /// The row at puzzle y
+    Row(i64),
+}
+
+/// A grid drawn as characters, with optional overlays and rulers
+///
+/// ```
+/// use aoc_common::grid::{Grid, Position, Render};
+///
+/// let grid: Grid<char> = "...\n.#.\n...".parse().unwrap();
+/// let text = Render::new(&grid)
+///     .mark([Position::new(0, 0), Position::new(1, 0)], 'X')
+///     .crop(Position::new(0, 0), Position::new(1, 1))
+///     .to_string();
+/// assert_eq!(text, "XX\n.#");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Render {
+    cells: Grid<Cell>,
+    /// Puzzle coordinates of the top-left cell
+    origin: Position,
+    rulers: bool,
+}
+
+impl Render {
+    /// Draw a character grid as is
+    pub fn new(grid: &Grid<char>) -> Self {
+        Self::from_grid(grid, |&ch| ch)
+    }
+
+    /// Draw each cell of `grid` with `f`
+    pub fn from_grid<T>(grid: &Grid<T>, mut f: impl FnMut(&T) -> char) -> Self {
+        Self {
+            cells: grid.map(|cell| Cell {
+                ch: f(cell),
+                color: None,
+            }),
+            origin: Position::new(0, 0),
+            rulers: false,
+        }
+    }
+
+    /// Draw nested rows, padding short rows with spaces
+    pub fn from_rows(rows: &[Vec<char>]) -> Self {
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let cells = rows
+            .iter()
+            .flat_map(|row| (0..width).map(|x| row.get(x).copied().unwrap_or(' ')))
+            .map(|ch| Cell { ch, color: None })
+            .collect();
+        Self {
+            cells: Grid::from_vec(width, rows.len(), cells).expect("rows were padded"),
+            origin: Position::new(0, 0),
+            rulers: false,
+        }
+    }
+
+    /// Draw the occupied region of a sparse grid, keeping its coordinates
+    pub fn from_sparse<T: Clone>(grid: &SparseGrid<T>, mut f: impl FnMut(&T) -> char) -> Self {
+        match grid.to_grid() {
+            Some((origin, dense)) => Self {
+                origin,
+                ..Self::from_grid(&dense, &mut f)
+            },
+            None => Self::from_rows(&[]),
+        }
+    }
+
+    fn cell_mut(&mut self, pos: Position) -> Option<&mut Cell> {
+        let local = Position::new(pos.x - self.origin.x, pos.y - self.origin.y);
+        self.cells.get_mut(local)
+    }
+
+    /// Draw `glyph` over every position in `positions`
+    ///
+    /// Positions outside the picture are ignored.
+    pub fn mark(mut self, positions: impl IntoIterator<Item = Position>, glyph: char) -> Self {
+        for pos in positions {
+            if let Some(cell) = self.cell_mut(pos) {
+                cell.ch = glyph;
+            }
+        }
+        self
+    }
+
+    /// Colour every position in `positions`, keeping its character
+    pub fn color(mut self, positions: impl IntoIterator<Item = Position>, color: Color) -> Self {
+        for pos in positions {
+            if let Some(cell) = self.cell_mut(pos) {
+                cell.color = Some(color);
+            }
+        }
+        self
+    }
+
+    /// Keep only the inclusive box from `min` to `max`
+    ///
+    /// The box is clamped to the picture, so it may come out smaller.
+    pub fn crop(self, min: Position, max: Position) -> Self {
+        let (width, height) = (self.cells.width() as i64, self.cells.height() as i64);
+        let x0 = (min.x - self.origin.x).clamp(0, width);
+        let y0 = (min.y - self.origin.y).clamp(0, height);
+        let x1 = (max.x - self.origin.x + 1).clamp(x0, width);
+        let y1 = (max.y - self.origin.y + 1).clamp(y0, height);
+
+        let cells = (y0..y1)
+            .flat_map(|y| (x0..x1).map(move |x| Position::new(x, y)))
+            .map(|pos| self.cells[pos])
+            .collect();
+        Self {
+            cells: Grid::from_vec((x1 - x0) as usize, (y1 - y0) as usize, cells)
+                .expect("cropped cells fill the box"),
+            origin: Position::new(self.origin.x + x0, self.origin.y + y0),
+            rulers: self.rulers,
+        }
+    }
+
+    /// Crop to the smallest box around `positions`
+    pub fn crop_to(self, positions: impl IntoIterator<Item = Position>) -> Self {
+        let bounds = positions
+            .into_iter()
+            .fold(None, |bounds, pos| match bounds {
+                None => Some((pos, pos)),
+                Some((min, max)) => Some((
+                    Position::new(pos.x.min(min.x), pos.y.min(min.y)),
+                    Position::new(pos.x.max(max.x), pos.y.max(max.y)),
+                )),
+            });
+        match bounds {
+            Some((min, max)) => self.crop(min, max),
+            None => self.crop(Position::new(0, 0), Position::new(-1, -1)),
+        }
+    }
+
+    /// Number the columns along the top and the rows down the left
+    ///
+    /// Columns show the last two digits of their number stacked, so every
+    /// label is one character wide. If any column is negative, a row of `-`
+    /// signs above the digits marks which ones.
+    pub fn rulers(mut self) -> Self {
+        self.rulers = true;
+        self
+    }
+
+    /// Positions whose character differs from `other`
+    ///
+    /// Only positions present in both pictures are compared.
+    pub fn differences(&self, other: &Render) -> Vec<Position> {
+        self.cells
+            .iter()
+            .filter_map(|(local, cell)| {
+                let pos = Position::new(local.x + self.origin.x, local.y + self.origin.y);
+                let theirs = other.cells.get(Position::new(
+                    pos.x - other.origin.x,
+                    pos.y - other.origin.y,
+                ))?;
+                (theirs.ch != cell.ch).then_some(pos)
+            })
+            .collect()
+    }
+
+    /// Draw `self` and `other` next to each other, row by row
+    ///
+    /// Rows are matched by puzzle y, so pictures with different origins or
+    /// rulers still line up; a row only one side has is blank on the other.
+    /// Rows of `self` that differ from `other` get a `!` between the two
+    /// pictures, so a mismatch stands out even without colour.
+    pub fn side_by_side(&self, other: &Render) -> String {
+        let changed: HashSet<i64> = self.differences(other).iter().map(|pos| pos.y).collect();
+        let (left, right) = (self.lines(), other.lines());
+        let header = self.header_height().max(other.header_height());
+        let ys = [self.rows(), other.rows()]
+            .into_iter()
+            .filter(|rows| !rows.is_empty());
+        let top = ys.clone().map(|rows| rows.start).min().unwrap_or(0);
+        let bottom = ys.map(|rows| rows.end).max().unwrap_or(0);
+        let blank = " ".repeat(self.line_width());
+
+        let headers = (0..header).map(|i| (None, Slot::Header(i)));
+        let rows = (top..bottom).map(|y| (Some(y), Slot::Row(y)));
+        headers
+            .chain(rows)
+            .map(|(y, slot)| {
+                let separator = match y {
+                    Some(y) if changed.contains(&y) => " ! ",
+                    _ => "   ",
+                };
+                let l = self.line(&left, header, slot).unwrap_or(&blank);
+                let r = other.line(&right, header, slot).unwrap_or("");
+                format!("{}{}{}", l, separator, r).trim_end().to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Puzzle y of the first row and one past the last
+    fn rows(&self) -> Range<i64> {
+        self.origin.y..self.origin.y + self.cells.height() as i64
+    }
+
+    /// The drawn line for `slot` out of `lines`, with the rulers pushed down
+    /// to the bottom of a `header` lines tall header
+    fn line<'a>(&self, lines: &'a [String], header: usize, slot: Slot) -> Option<&'a str> {
+        let index = match slot {
+            Slot::Header(i) => i.checked_sub(header - self.header_height())?,
+            Slot::Row(y) if self.rows().contains(&y) => {
+                self.header_height() + (y - self.origin.y) as usize
+            }
+            Slot::Row(_) => return None,
+        };
+        lines.get(index).map(String::as_str)
+    }
+
+    fn header_height(&self) -> usize {
+        match self.rulers {
+            true if self.origin.x < 0 => 3,
+            true => 2,
+            false => 0,
+        }
+    }
+
+    fn gutter_width(&self) -> usize {
+        if !self.rulers || self.cells.height() == 0 {
+            return 0;
+        }
+        let first = self.origin.y.to_string().len();
+        let last = (self.origin.y + self.cells.height() as i64 - 1)
+            .to_string()
+            .len();
+        first.max(last) + 1
+    }
+
+    /// Visible width of every line, ignoring colour codes
+    fn line_width(&self) -> usize {
+        self.gutter_width() + self.cells.width()
+    }
+
+    fn lines(&self) -> Vec<String> {
+        let gutter = self.gutter_width();
+        let mut lines = Vec::with_capacity(self.header_height() + self.cells.height());
+
+        if self.rulers {
+            let xs = self.origin.x..self.origin.x + self.cells.width() as i64;
+            let digit = |n: i64| char::from_digit((n.abs() % 10) as u32, 10).unwrap();
+            if self.origin.x < 0 {
+                let signs: String = xs.clone().map(|x| if x < 0 { '-' } else { ' ' }).collect();
+                lines.push(format!("{:gutter$}{}", "", signs));
+            }
+            let tens: String = xs.clone().map(|x| digit(x / 10)).collect();
+            let units: String = xs.map(digit).collect();
+            lines.push(format!("{:gutter$}{}", "", tens));
+            lines.push(format!("{:gutter$}{}", "", units));
+        }
+
+        for (i, row) in self.cells.rows().enumerate() {
+            let mut line = String::new();
+            if self.rulers {
+                let y = self.origin.y + i as i64;
+                line.push_str(&format!("{:>width$} ", y, width = gutter - 1));
+            }
+            for cell in row {
+                match cell.color {
+                    Some(color) => {
+                        line.push_str(&format!("\x1b[{}m{}\x1b[0m", color.ansi_code(), cell.ch))
+                    }
+                    None => line.push(cell.ch),
+                }
+            }
+            lines.push(line);
+        }
+        lines
+    }
+}
+
+impl From<&Grid<char>> for Render {
+    fn from(grid: &Grid<char>) -> Self {
+        Self::new(grid)
+    }
+}
+
+impl From<&[Vec<char>]> for Render {
+    fn from(rows: &[Vec<char>]) -> Self {
+        Self::from_rows(rows)
+    }
+}
+
+impl fmt::Display for Render {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.lines().join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "#...\n.^..\n..#.";
+
+    fn example() -> Grid<char> {
+        EXAMPLE.parse().unwrap()
+    }
+
+    #[test]
+    fn test_plain_and_rows_agree() {
+        let rows: Vec<Vec<char>> = EXAMPLE.lines().map(|l| l.chars().collect()).collect();
+        assert_eq!(Render::new(&example()).to_string(), EXAMPLE);
+        assert_eq!(Render::from_rows(&rows).to_string(), EXAMPLE);
+        assert_eq!(
+            Render::from_rows(&[vec!['a'], vec!['b', 'c']]).to_string(),
+            "a \nbc"
+        );
+    }
+
+    #[test]
+    fn test_mark_and_color() {
+        let render = Render::new(&example())
+            .mark([Position::new(2, 1), Position::new(9, 9)], 'X')
+            .color([Position::new(0, 0)], Color::Red);
+        assert_eq!(render.to_string(), "\x1b[31m#\x1b[0m...\n.^X.\n..#.");
+    }
+
+    #[test]
+    fn test_crop_keeps_coordinates() {
+        let render = Render::new(&example())
+            .crop(Position::new(1, 1), Position::new(10, 10))
+            .mark([Position::new(2, 2)], 'X');
+        assert_eq!(render.to_string(), "^..\n.X.");
+
+        let empty = Render::new(&example()).crop_to([]);
+        assert_eq!(empty.to_string(), "");
+    }
+
+    #[test]
+    fn test_rulers() {
+        let grid = Grid::new(12, 2, '.');
+        let render = Render::new(&grid).crop(Position::new(8, 0), Position::new(11, 1));
+        assert_eq!(
+            render.rulers().to_string(),
+            "  0011\n  8901\n0 ....\n1 ...."
+        );
+
+        let mut sparse = SparseGrid::new('.');
+        sparse.insert(Position::new(-11, 0), '#');
+        sparse.insert(Position::new(1, 0), '#');
+        assert_eq!(
+            Render::from_sparse(&sparse, |&c| c).rulers().to_string(),
+            "  -----------  \n  1100000000000\n  1098765432101\n0 #...........#"
+        );
+    }
+
+    #[test]
+    fn test_sparse_origin_and_side_by_side() {
+        let mut sparse = SparseGrid::new('.');
+        sparse.insert(Position::new(-1, -1), '#');
+        sparse.insert(Position::new(1, 0), '#');
+        let before = Render::from_sparse(&sparse, |&c| c);
+        sparse.insert(Position::new(0, 0), 'o');
+        let after = Render::from_sparse(&sparse, |&c| c);
+
+        assert_eq!(before.differences(&after), vec![Position::new(0, 0)]);
+        assert_eq!(before.side_by_side(&after), "#..   #..\n..# ! .o#");
+        assert_eq!(
+            before.rulers().side_by_side(&after.rulers()),
+            "   -        -\n   000      000\n   101      101\n-1 #..   -1 #..\n 0 ..# !  0 .o#"
+        );
+    }
+
+    #[test]
+    fn test_side_by_side_aligns_rows_by_y() {
+        let mut sparse = SparseGrid::new('.');
+        sparse.insert(Position::new(0, 0), '#');
+        sparse.insert(Position::new(2, 1), '#');
+        let before = Render::from_sparse(&sparse, |&c| c);
+        sparse.insert(Position::new(1, -1), 'o');
+        sparse.insert(Position::new(1, 1), 'o');
+        let after = Render::from_sparse(&sparse, |&c| c);
+
+        assert_eq!(before.differences(&after), vec![Position::new(1, 1)]);
+        assert_eq!(
+            before.side_by_side(&after),
+            "      .o.\n#..   #..\n..# ! .o#"
+        );
+        // Only the left side has rulers, so its rows start lower
+        assert_eq!(
+            before.rulers().side_by_side(&after),
+            "  000\n  012\n        .o.\n0 #..   #..\n1 ..# ! .o#"
+        );
+    }
+}
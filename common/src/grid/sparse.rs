@@ -0,0 +1,393 @@
+// Unbounded grid stored as a hash map from position to cell
+//
+// Only cells that were written take up space; every other position reads as
+// the grid's default value. The bounding box of the written cells is kept up
+// to date so the occupied region can be rendered or copied into a `Grid`.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use super::{Grid, Position};
+
+/// Grid without fixed bounds, for simulations that grow in any direction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Position, T>,
+    default: T,
+    /// Inclusive corners around every stored cell
+    bounds: Option<(Position, Position)>,
+}
+
+impl<T> SparseGrid<T> {
+    /// Empty grid where every position reads as `default`
+    pub fn new(default: T) -> Self {
+        Self {
+            cells: HashMap::new(),
+            default,
+            bounds: None,
+        }
+    }
+
+    /// Store the cells of `grid` that differ from `default`
+    pub fn from_grid(grid: &Grid<T>, default: T) -> Self
+    where
+        T: Clone + PartialEq,
+    {
+        let mut sparse = Self::new(default);
+        for (pos, cell) in grid.iter() {
+            if *cell != sparse.default {
+                sparse.insert(pos, cell.clone());
+            }
+        }
+        sparse
+    }
+
+    /// Number of stored cells
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The value read at positions that were never written
+    pub fn default_value(&self) -> &T {
+        &self.default
+    }
+
+    /// Whether a cell is stored at `pos`
+    pub fn contains(&self, pos: Position) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    /// The cell at `pos`, or the default if nothing is stored there
+    pub fn get(&self, pos: Position) -> &T {
+        self.cells.get(&pos).unwrap_or(&self.default)
+    }
+
+    /// The stored cell at `pos`, inserting the default first if needed
+    pub fn get_mut(&mut self, pos: Position) -> &mut T
+    where
+        T: Clone,
+    {
+        if !self.cells.contains_key(&pos) {
+            self.grow(pos);
+        }
+        self.cells
+            .entry(pos)
+            .or_insert_with(|| self.default.clone())
+    }
+
+    /// Store `value` at `pos`, returning what was stored there before
+    pub fn insert(&mut self, pos: Position, value: T) -> Option<T> {
+        self.grow(pos);
+        self.cells.insert(pos, value)
+    }
+
+    /// Forget the cell at `pos` so it reads as the default again
+    pub fn remove(&mut self, pos: Position) -> Option<T> {
+        let removed = self.cells.remove(&pos)?;
+        if let Some((min, max)) = self.bounds
+            && (pos.x == min.x || pos.x == max.x || pos.y == min.y || pos.y == max.y)
+        {
+            self.recompute_bounds();
+        }
+        Some(removed)
+    }
+
+    /// Remove every cell
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.bounds = None;
+    }
+
+    /// Keep only the cells for which `f` returns true
+    pub fn retain(&mut self, mut f: impl FnMut(Position, &T) -> bool) {
+        self.cells.retain(|&pos, cell| f(pos, cell));
+        self.recompute_bounds();
+    }
+
+    /// Inclusive top-left and bottom-right corners around the stored cells
+    pub fn bounds(&self) -> Option<(Position, Position)> {
+        self.bounds
+    }
+
+    /// Width of the bounding box (0 when empty)
+    pub fn width(&self) -> usize {
+        self.bounds
+            .map_or(0, |(min, max)| (max.x - min.x + 1) as usize)
+    }
+
+    /// Height of the bounding box (0 when empty)
+    pub fn height(&self) -> usize {
+        self.bounds
+            .map_or(0, |(min, max)| (max.y - min.y + 1) as usize)
+    }
+
+    /// Stored positions in no particular order
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        self.cells.keys().copied()
+    }
+
+    /// Stored cells with their positions in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.cells.iter().map(|(&pos, cell)| (pos, cell))
+    }
+
+    /// How many of the 4 orthogonal neighbours of `pos` satisfy `f`
+    ///
+    /// Unwritten neighbours are tested with the default value.
+    pub fn count_neighbors4(&self, pos: Position, mut f: impl FnMut(&T) -> bool) -> usize {
+        pos.neighbors4().filter(|&n| f(self.get(n))).count()
+    }
+
+    /// How many of the 8 surrounding neighbours of `pos` satisfy `f`
+    ///
+    /// Unwritten neighbours are tested with the default value.
+    pub fn count_neighbors8(&self, pos: Position, mut f: impl FnMut(&T) -> bool) -> usize {
+        pos.neighbors8().filter(|&n| f(self.get(n))).count()
+    }
+
+    /// For every position next to a stored cell satisfying `f`, how many
+    /// such cells surround it
+    ///
+    /// This is the one pass a game-of-life step needs: positions missing
+    /// from the map have no live neighbours.
+    pub fn neighbor_counts8(&self, mut f: impl FnMut(&T) -> bool) -> HashMap<Position, usize> {
+        let mut counts = HashMap::new();
+        for (pos, cell) in self.iter() {
+            if f(cell) {
+                for n in pos.neighbors8() {
+                    *counts.entry(n).or_insert(0) += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    /// Copy the bounding box into a dense grid, along with the position of
+    /// its top-left cell
+    pub fn to_grid(&self) -> Option<(Position, Grid<T>)>
+    where
+        T: Clone,
+    {
+        let (min, _) = self.bounds?;
+        let mut grid = Grid::new(self.width(), self.height(), self.default.clone());
+        for (pos, cell) in self.iter() {
+            grid[Position::new(pos.x - min.x, pos.y - min.y)] = cell.clone();
+        }
+        Some((min, grid))
+    }
+
+    /// Draw the bounding box with one character per cell
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+        let Some((min, max)) = self.bounds else {
+            return String::new();
+        };
+
+        let mut out = String::with_capacity((self.width() + 1) * self.height());
+        for y in min.y..=max.y {
+            if y > min.y {
+                out.push('\n');
+            }
+            for x in min.x..=max.x {
+                out.push(f(self.get(Position::new(x, y))));
+            }
+        }
+        out
+    }
+
+    fn grow(&mut self, pos: Position) {
+        self.bounds = Some(match self.bounds {
+            None => (pos, pos),
+            Some((min, max)) => (
+                Position::new(min.x.min(pos.x), min.y.min(pos.y)),
+                Position::new(max.x.max(pos.x), max.y.max(pos.y)),
+            ),
+        });
+    }
+
+    fn recompute_bounds(&mut self) {
+        self.bounds = None;
+        let positions: Vec<Position> = self.cells.keys().copied().collect();
+        for pos in positions {
+            self.grow(pos);
+        }
+    }
+}
+
+impl<T: Default> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: Default> FromIterator<(Position, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Position, T)>>(iter: I) -> Self {
+        let mut grid = Self::default();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<(Position, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Position, T)>>(&mut self, iter: I) {
+        for (pos, value) in iter {
+            self.insert(pos, value);
+        }
+    }
+}
+
+impl<T> std::ops::Index<Position> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Position) -> &T {
+        self.get(pos)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((min, max)) = self.bounds else {
+            return Ok(());
+        };
+        for y in min.y..=max.y {
+            if y > min.y {
+                writeln!(f)?;
+            }
+            for x in min.x..=max.x {
+                write!(f, "{}", self.get(Position::new(x, y)))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// One game-of-life generation over the live cells
+    fn life_step(grid: &SparseGrid<bool>) -> SparseGrid<bool> {
+        grid.neighbor_counts8(|&alive| alive)
+            .into_iter()
+            .filter(|&(pos, count)| count == 3 || (count == 2 && *grid.get(pos)))
+            .map(|(pos, _)| (pos, true))
+            .collect()
+    }
+
+    #[test]
+    fn test_default_reads_and_bounds() {
+        let mut grid = SparseGrid::new('.');
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.render(|&c| c), "");
+
+        grid.insert(Position::new(-2, 3), '#');
+        grid.insert(Position::new(1, -1), 'o');
+        assert_eq!(*grid.get(Position::new(100, -100)), '.');
+        assert_eq!(grid[Position::new(-2, 3)], '#');
+        assert_eq!(
+            grid.bounds(),
+            Some((Position::new(-2, -1), Position::new(1, 3)))
+        );
+        assert_eq!((grid.width(), grid.height()), (4, 5));
+        assert_eq!(grid.to_string(), "...o\n....\n....\n....\n#...");
+
+        grid.remove(Position::new(-2, 3));
+        assert_eq!(
+            grid.bounds(),
+            Some((Position::new(1, -1), Position::new(1, -1)))
+        );
+        assert_eq!(grid.to_string(), "o");
+    }
+
+    #[test]
+    fn test_get_mut_inserts_default() {
+        let mut grid: SparseGrid<u32> = SparseGrid::default();
+        *grid.get_mut(Position::new(5, 5)) += 2;
+        *grid.get_mut(Position::new(5, 5)) += 3;
+        assert_eq!(grid[Position::new(5, 5)], 5);
+        assert_eq!(grid.len(), 1);
+        assert_eq!(
+            grid.bounds(),
+            Some((Position::new(5, 5), Position::new(5, 5)))
+        );
+    }
+
+    #[test]
+    fn test_neighbor_counting() {
+        let grid: SparseGrid<bool> = [(0, 0), (1, 0), (0, 1)]
+            .into_iter()
+            .map(|(x, y)| (Position::new(x, y), true))
+            .collect();
+        assert_eq!(grid.count_neighbors8(Position::new(1, 1), |&b| b), 3);
+        assert_eq!(grid.count_neighbors4(Position::new(1, 1), |&b| b), 2);
+        assert_eq!(grid.count_neighbors4(Position::new(0, 0), |&b| !b), 2);
+    }
+
+    #[test]
+    fn test_glider_moves_diagonally() {
+        let glider = ".#.\n..#\n###";
+        let grid: Grid<bool> = Grid::from_str_with(glider, |c| c == '#').unwrap();
+        let mut life = SparseGrid::from_grid(&grid, false);
+        let render = |g: &SparseGrid<bool>| g.render(|&alive| if alive { '#' } else { '.' });
+
+        for _ in 0..4 {
+            life = life_step(&life);
+        }
+        assert_eq!(render(&life), glider);
+        assert_eq!(
+            life.bounds(),
+            Some((Position::new(1, 1), Position::new(3, 3)))
+        );
+    }
+
+    #[test]
+    fn test_to_grid_round_trip() {
+        let mut sparse = SparseGrid::new(0u8);
+        sparse.insert(Position::new(-1, -1), 1);
+        sparse.insert(Position::new(1, 0), 2);
+
+        let (origin, grid) = sparse.to_grid().unwrap();
+        assert_eq!(origin, Position::new(-1, -1));
+        assert_eq!(grid.to_string(), "100\n002");
+
+        let back = SparseGrid::from_grid(&grid, 0);
+        assert_eq!(back.len(), 2);
+        assert_eq!(back[Position::new(2, 1)], 2);
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_bounds_match_stored_cells(
+            ops in prop::collection::vec((any::<bool>(), -5i64..5, -5i64..5), 0..60),
+        ) {
+            let mut grid = SparseGrid::new(0);
+            for (insert, x, y) in ops {
+                let pos = Position::new(x, y);
+                if insert {
+                    grid.insert(pos, 1);
+                } else {
+                    grid.remove(pos);
+                }
+            }
+
+            let expected = grid.positions().fold(None, |bounds, pos| match bounds {
+                None => Some((pos, pos)),
+                Some((min, max)) => Some((
+                    Position::new(pos.x.min(min.x), pos.y.min(min.y)),
+                    Position::new(pos.x.max(max.x), pos.y.max(max.y)),
+                )),
+            });
+            prop_assert_eq!(grid.bounds(), expected);
+            prop_assert_eq!(grid.render(|&n| if n == 1 { '#' } else { '.' }).matches('#').count(), grid.len());
+        }
+    }
+}
@@ -0,0 +1,209 @@
+// Rotations and reflections of whole grids
+//
+// The eight symmetries of a rectangle (the dihedral group of the square)
+// are one enum, so "try every orientation" is a loop over `Symmetry::ALL`.
+// Each symmetry is a mapping from output positions back to input ones;
+// every transform builds a new grid by pulling cells through it.
+
+use super::{Grid, Position};
+
+/// One of the eight ways to rotate and/or reflect a grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    Identity,
+    /// 90 degrees clockwise
+    RotateRight,
+    Rotate180,
+    /// 90 degrees counter-clockwise
+    RotateLeft,
+    /// Mirror left to right
+    FlipHorizontal,
+    /// Mirror top to bottom
+    FlipVertical,
+    /// Mirror across the main (top-left to bottom-right) diagonal
+    Transpose,
+    /// Mirror across the anti (top-right to bottom-left) diagonal
+    AntiTranspose,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::Identity,
+        Symmetry::RotateRight,
+        Symmetry::Rotate180,
+        Symmetry::RotateLeft,
+        Symmetry::FlipHorizontal,
+        Symmetry::FlipVertical,
+        Symmetry::Transpose,
+        Symmetry::AntiTranspose,
+    ];
+
+    /// The four rotations, without reflections
+    pub const ROTATIONS: [Symmetry; 4] = [
+        Symmetry::Identity,
+        Symmetry::RotateRight,
+        Symmetry::Rotate180,
+        Symmetry::RotateLeft,
+    ];
+
+    /// Whether the result has width and height swapped
+    pub fn swaps_axes(&self) -> bool {
+        matches!(
+            self,
+            Symmetry::RotateRight
+                | Symmetry::RotateLeft
+                | Symmetry::Transpose
+                | Symmetry::AntiTranspose
+        )
+    }
+
+    /// The symmetry that undoes this one
+    pub fn inverse(&self) -> Symmetry {
+        match self {
+            Symmetry::RotateRight => Symmetry::RotateLeft,
+            Symmetry::RotateLeft => Symmetry::RotateRight,
+            other => *other,
+        }
+    }
+
+    /// Where `pos` in a `width` x `height` grid ends up after the transform
+    pub fn apply_to_position(&self, pos: Position, width: usize, height: usize) -> Position {
+        let (w, h) = (width as i64, height as i64);
+        let (x, y) = (pos.x, pos.y);
+        let (x, y) = match self {
+            Symmetry::Identity => (x, y),
+            Symmetry::RotateRight => (h - 1 - y, x),
+            Symmetry::Rotate180 => (w - 1 - x, h - 1 - y),
+            Symmetry::RotateLeft => (y, w - 1 - x),
+            Symmetry::FlipHorizontal => (w - 1 - x, y),
+            Symmetry::FlipVertical => (x, h - 1 - y),
+            Symmetry::Transpose => (y, x),
+            Symmetry::AntiTranspose => (h - 1 - y, w - 1 - x),
+        };
+        Position::new(x, y)
+    }
+
+    /// Transform a whole grid
+    pub fn apply<T: Clone>(&self, grid: &Grid<T>) -> Grid<T> {
+        let (width, height) = if self.swaps_axes() {
+            (grid.height, grid.width)
+        } else {
+            (grid.width, grid.height)
+        };
+
+        // Pull each output cell from where the inverse sends it
+        let inverse = self.inverse();
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Position::from((x, y))))
+            .map(|pos| grid[inverse.apply_to_position(pos, width, height)].clone())
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Swap rows and columns
+    pub fn transpose(&self) -> Grid<T> {
+        Symmetry::Transpose.apply(self)
+    }
+
+    /// Rotate 90 degrees clockwise
+    pub fn rotate_right(&self) -> Grid<T> {
+        Symmetry::RotateRight.apply(self)
+    }
+
+    /// Rotate 90 degrees counter-clockwise
+    pub fn rotate_left(&self) -> Grid<T> {
+        Symmetry::RotateLeft.apply(self)
+    }
+
+    pub fn rotate_180(&self) -> Grid<T> {
+        Symmetry::Rotate180.apply(self)
+    }
+
+    /// Mirror left to right
+    pub fn flip_horizontal(&self) -> Grid<T> {
+        Symmetry::FlipHorizontal.apply(self)
+    }
+
+    /// Mirror top to bottom
+    pub fn flip_vertical(&self) -> Grid<T> {
+        Symmetry::FlipVertical.apply(self)
+    }
+
+    /// All eight orientations, in `Symmetry::ALL` order
+    ///
+    /// Symmetric grids produce some duplicates.
+    pub fn symmetries(&self) -> impl Iterator<Item = (Symmetry, Grid<T>)> {
+        Symmetry::ALL.into_iter().map(|s| (s, s.apply(self)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Grid<char> {
+        "abc\ndef".parse().unwrap()
+    }
+
+    #[test]
+    fn test_each_symmetry() {
+        let grid = example();
+        let cases = [
+            (Symmetry::Identity, "abc\ndef"),
+            (Symmetry::RotateRight, "da\neb\nfc"),
+            (Symmetry::Rotate180, "fed\ncba"),
+            (Symmetry::RotateLeft, "cf\nbe\nad"),
+            (Symmetry::FlipHorizontal, "cba\nfed"),
+            (Symmetry::FlipVertical, "def\nabc"),
+            (Symmetry::Transpose, "ad\nbe\ncf"),
+            (Symmetry::AntiTranspose, "fc\neb\nda"),
+        ];
+        for (symmetry, expected) in cases {
+            assert_eq!(
+                symmetry.apply(&grid).to_string(),
+                expected,
+                "{:?}",
+                symmetry
+            );
+        }
+        assert_eq!(grid.rotate_right().to_string(), "da\neb\nfc");
+        assert_eq!(grid.symmetries().count(), 8);
+    }
+
+    #[test]
+    fn test_positions_follow_their_cells() {
+        let grid = example();
+        for symmetry in Symmetry::ALL {
+            let out = symmetry.apply(&grid);
+            for (pos, cell) in grid.iter() {
+                let moved = symmetry.apply_to_position(pos, grid.width(), grid.height());
+                assert_eq!(out[moved], *cell, "{:?} at {:?}", symmetry, pos);
+            }
+            assert_eq!(symmetry.inverse().apply(&out), grid);
+        }
+    }
+
+    #[test]
+    fn test_rotations_compose() {
+        let grid = example();
+        assert_eq!(grid.rotate_right().rotate_right(), grid.rotate_180());
+        assert_eq!(
+            grid.rotate_left().rotate_left().rotate_left(),
+            grid.rotate_right()
+        );
+        assert_eq!(grid.flip_horizontal().flip_vertical(), grid.rotate_180());
+        assert_eq!(grid.rotate_right().flip_horizontal(), grid.transpose());
+    }
+
+    #[test]
+    fn test_empty_grid() {
+        let grid: Grid<char> = "".parse().unwrap();
+        assert_eq!(grid.rotate_right().width(), 0);
+    }
+}
@@ -0,0 +1,243 @@
+// Borrowed windows into a grid and straight-line walks across it
+//
+// A `GridView` is a rectangle of another grid, addressed from its own
+// top-left corner, so pattern matching code can treat every window the same.
+// A `Line` walks from a start cell in one direction until it leaves the
+// grid; rows, columns and both diagonal families are built from it.
+
+use super::{Direction8, Grid, Position};
+
+/// A rectangular window into a `Grid`, without copying
+#[derive(Debug, Clone, Copy)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    /// Top-left corner in the underlying grid
+    origin: Position,
+    width: usize,
+    height: usize,
+}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Top-left corner in the underlying grid
+    pub fn origin(&self) -> Position {
+        self.origin
+    }
+
+    /// The cell at `pos`, relative to the window's top-left corner
+    pub fn get(&self, pos: Position) -> Option<&'a T> {
+        let inside = pos.x >= 0
+            && pos.y >= 0
+            && (pos.x as usize) < self.width
+            && (pos.y as usize) < self.height;
+        inside.then(|| &self.grid[Position::new(self.origin.x + pos.x, self.origin.y + pos.y)])
+    }
+
+    /// Rows as slices of the underlying grid
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> + use<'a, T> {
+        let (grid, origin, width) = (self.grid, self.origin, self.width);
+        (0..self.height).map(move |y| {
+            let start = (origin.y as usize + y) * grid.width + origin.x as usize;
+            &grid.cells[start..start + width]
+        })
+    }
+
+    /// Cells with their window-relative positions, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &'a T)> + use<'a, T> {
+        self.rows().enumerate().flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(x, cell)| (Position::from((x, y)), cell))
+        })
+    }
+
+    /// Copy the window into its own grid
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.rows().flatten().cloned().collect(),
+        }
+    }
+}
+
+/// Cells from a start position in one direction, up to the grid's edge
+#[derive(Debug, Clone)]
+pub struct Line<'a, T> {
+    grid: &'a Grid<T>,
+    pos: Position,
+    direction: Direction8,
+}
+
+impl<T> Line<'_, T> {
+    /// Position of the next cell to be yielded
+    pub fn position(&self) -> Position {
+        self.pos
+    }
+}
+
+impl<'a, T> Iterator for Line<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let cell = self.grid.get(self.pos)?;
+        self.pos = self.pos.step8(self.direction);
+        Some(cell)
+    }
+}
+
+impl<T> Grid<T> {
+    /// The `width` x `height` window with its top-left corner at `origin`,
+    /// or `None` if it doesn't fit
+    pub fn view(&self, origin: Position, width: usize, height: usize) -> Option<GridView<'_, T>> {
+        let fits = origin.x >= 0
+            && origin.y >= 0
+            && origin.x as usize + width <= self.width
+            && origin.y as usize + height <= self.height;
+        fits.then_some(GridView {
+            grid: self,
+            origin,
+            width,
+            height,
+        })
+    }
+
+    /// Every `width` x `height` window, row by row
+    pub fn windows(&self, width: usize, height: usize) -> impl Iterator<Item = GridView<'_, T>> {
+        let xs = (self.width + 1).saturating_sub(width);
+        let ys = (self.height + 1).saturating_sub(height);
+        (0..ys).flat_map(move |y| {
+            (0..xs).map(move |x| GridView {
+                grid: self,
+                origin: Position::from((x, y)),
+                width,
+                height,
+            })
+        })
+    }
+
+    /// Walk from `start` in `direction` until leaving the grid
+    pub fn line(&self, start: Position, direction: Direction8) -> Line<'_, T> {
+        Line {
+            grid: self,
+            pos: start,
+            direction,
+        }
+    }
+
+    /// The cells of column `x`, top to bottom
+    pub fn column(&self, x: usize) -> Line<'_, T> {
+        self.line(Position::from((x, 0)), Direction8::SOUTH)
+    }
+
+    /// Every row, left to right
+    pub fn row_lines(&self) -> impl Iterator<Item = Line<'_, T>> {
+        (0..self.height).map(|y| self.line(Position::from((0, y)), Direction8::EAST))
+    }
+
+    /// Every column, top to bottom
+    pub fn columns(&self) -> impl Iterator<Item = Line<'_, T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Every down-right (↘) diagonal, starting from the bottom-left corner
+    pub fn diagonals(&self) -> impl Iterator<Item = Line<'_, T>> {
+        let down_left = (1..self.height).rev().map(|y| Position::from((0, y)));
+        let top = (0..self.width).map(|x| Position::from((x, 0)));
+        down_left
+            .chain(top)
+            .map(|start| self.line(start, Direction8::SOUTHEAST))
+    }
+
+    /// Every down-left (↙) diagonal, starting from the top-left corner
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = Line<'_, T>> {
+        let top = (0..self.width).map(|x| Position::from((x, 0)));
+        let right = (1..self.height).map(|y| Position::from((self.width.saturating_sub(1), y)));
+        let right = right.filter(|_| self.width > 0);
+        top.chain(right)
+            .map(|start| self.line(start, Direction8::SOUTHWEST))
+    }
+
+    /// Rows, columns, diagonals and anti-diagonals, each read forwards
+    ///
+    /// Reverse each line (or search for a reversed pattern) to cover the
+    /// other four directions.
+    pub fn all_lines(&self) -> impl Iterator<Item = Line<'_, T>> {
+        self.row_lines()
+            .chain(self.columns())
+            .chain(self.diagonals())
+            .chain(self.anti_diagonals())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Grid<char> {
+        "abcd\nefgh\nijkl".parse().unwrap()
+    }
+
+    fn strings<'a>(lines: impl Iterator<Item = Line<'a, char>>) -> Vec<String> {
+        lines.map(|line| line.collect()).collect()
+    }
+
+    #[test]
+    fn test_view() {
+        let grid = example();
+        let view = grid.view(Position::new(1, 1), 3, 2).unwrap();
+        assert_eq!(view.get(Position::new(0, 0)), Some(&'f'));
+        assert_eq!(view.get(Position::new(2, 1)), Some(&'l'));
+        assert_eq!(view.get(Position::new(3, 0)), None);
+        assert_eq!(view.to_grid().to_string(), "fgh\njkl");
+        assert!(grid.view(Position::new(2, 0), 3, 1).is_none());
+        assert!(grid.view(Position::new(-1, 0), 1, 1).is_none());
+    }
+
+    #[test]
+    fn test_windows() {
+        let grid = example();
+        let windows: Vec<String> = grid
+            .windows(2, 2)
+            .map(|view| view.to_grid().to_string())
+            .collect();
+        assert_eq!(windows.len(), 6);
+        assert_eq!(windows[0], "ab\nef");
+        assert_eq!(windows[5], "gh\nkl");
+        assert_eq!(grid.windows(5, 1).count(), 0);
+    }
+
+    #[test]
+    fn test_lines() {
+        let grid = example();
+        assert_eq!(strings(grid.row_lines()), ["abcd", "efgh", "ijkl"]);
+        assert_eq!(strings(grid.columns()), ["aei", "bfj", "cgk", "dhl"]);
+        assert_eq!(
+            strings(grid.diagonals()),
+            ["i", "ej", "afk", "bgl", "ch", "d"]
+        );
+        assert_eq!(
+            strings(grid.anti_diagonals()),
+            ["a", "be", "cfi", "dgj", "hk", "l"]
+        );
+
+        let cells: usize = grid.all_lines().map(Iterator::count).sum();
+        assert_eq!(cells, 4 * 12);
+    }
+
+    #[test]
+    fn test_lines_of_empty_grid() {
+        let grid: Grid<char> = "".parse().unwrap();
+        assert_eq!(grid.all_lines().count(), 0);
+    }
+}
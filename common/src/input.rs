@@ -0,0 +1,191 @@
+// Runtime loading of puzzle inputs
+//
+// Inputs are read when the solution runs instead of being embedded with
+// `include_str!`, so a fresh clone without `data/inputs/` still builds and
+// can run the example tests.
+
+use std::fmt;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Where to read the puzzle input from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// `data/inputs/NN.txt` relative to the year crate
+    Default,
+    /// An explicit file (`--input <path>`)
+    File(PathBuf),
+    /// Standard input (`--input -`)
+    Stdin,
+}
+
+impl Source {
+    /// `-` means stdin, anything else is a path
+    pub fn parse(value: &str) -> Self {
+        if value == "-" {
+            Source::Stdin
+        } else {
+            Source::File(PathBuf::from(value))
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    /// The default input file does not exist
+    Missing { path: PathBuf, download: String },
+    /// The default input file exists but is empty
+    Empty { path: PathBuf, download: String },
+    /// Reading a file or stdin failed
+    Io {
+        source_name: String,
+        error: io::Error,
+    },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Missing { path, download } => write!(
+                f,
+                "Input file not found at {}\n  Download it with: {}",
+                path.display(),
+                download
+            ),
+            InputError::Empty { path, download } => write!(
+                f,
+                "Input file at {} is empty\n  Download it with: {}",
+                path.display(),
+                download
+            ),
+            InputError::Io { source_name, error } => {
+                write!(f, "Failed to read input from {}: {}", source_name, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// Path of a day's input, e.g. `<manifest_dir>/data/inputs/01.txt`
+pub fn input_path(manifest_dir: impl AsRef<Path>, day: &str) -> PathBuf {
+    manifest_dir
+        .as_ref()
+        .join("data/inputs")
+        .join(format!("{}.txt", day))
+}
+
+/// The `aoc download` command that fetches this input
+fn download_command(manifest_dir: &Path, day: &str) -> String {
+    let day = day.trim_start_matches('0');
+    let year = manifest_dir
+        .file_name()
+        .and_then(|name| name.to_str())
+        .filter(|name| name.len() == 4 && name.parse::<u16>().is_ok());
+
+    match year {
+        Some(year) => format!("aoc download {} -y {}", day, year),
+        None => format!("aoc download {}", day),
+    }
+}
+
+/// Read a day's input from the given source
+pub fn read(
+    manifest_dir: impl AsRef<Path>,
+    day: &str,
+    source: &Source,
+) -> Result<String, InputError> {
+    let manifest_dir = manifest_dir.as_ref();
+
+    match source {
+        Source::Default => {
+            let path = input_path(manifest_dir, day);
+            let download = download_command(manifest_dir, day);
+            match std::fs::read_to_string(&path) {
+                Ok(input) if input.trim().is_empty() => Err(InputError::Empty { path, download }),
+                Ok(input) => Ok(input),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    Err(InputError::Missing { path, download })
+                }
+                Err(error) => Err(InputError::Io {
+                    source_name: path.display().to_string(),
+                    error,
+                }),
+            }
+        }
+        Source::File(path) => std::fs::read_to_string(path).map_err(|error| InputError::Io {
+            source_name: path.display().to_string(),
+            error,
+        }),
+        Source::Stdin => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|error| InputError::Io {
+                    source_name: "stdin".to_string(),
+                    error,
+                })?;
+            Ok(input)
+        }
+    }
+}
+
+/// Like `read`, but print the error and exit instead of returning it
+pub fn read_or_exit(manifest_dir: impl AsRef<Path>, day: &str, source: &Source) -> String {
+    read(manifest_dir, day, source).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_read_default_input() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("data/inputs")).unwrap();
+        std::fs::write(dir.path().join("data/inputs/01.txt"), "1 2\n").unwrap();
+
+        assert_eq!(read(dir.path(), "01", &Source::Default).unwrap(), "1 2\n");
+    }
+
+    #[test]
+    fn test_missing_input_names_download_command() {
+        let dir = TempDir::new().unwrap();
+        let year_dir = dir.path().join("2024");
+
+        let err = read(&year_dir, "07", &Source::Default).unwrap_err();
+        assert!(matches!(err, InputError::Missing { .. }));
+        assert!(err.to_string().contains("aoc download 7 -y 2024"));
+    }
+
+    #[test]
+    fn test_empty_input_is_an_error() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("data/inputs")).unwrap();
+        std::fs::write(dir.path().join("data/inputs/12.txt"), "\n").unwrap();
+
+        let err = read(dir.path(), "12", &Source::Default).unwrap_err();
+        assert!(matches!(err, InputError::Empty { .. }));
+        assert!(err.to_string().contains("aoc download 12"));
+    }
+
+    #[test]
+    fn test_read_explicit_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("custom.txt");
+        std::fs::write(&path, "custom").unwrap();
+
+        assert_eq!(
+            read(dir.path(), "01", &Source::File(path)).unwrap(),
+            "custom"
+        );
+        assert!(matches!(
+            read(dir.path(), "01", &Source::File(dir.path().join("nope.txt"))),
+            Err(InputError::Io { .. })
+        ));
+    }
+}
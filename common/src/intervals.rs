@@ -0,0 +1,439 @@
+// Sets of half-open ranges `[start, end)`
+//
+// `IntervalSet` keeps its ranges sorted, disjoint and non-adjacent, so two
+// sets with the same members always store the same ranges and compare equal.
+// `OffsetMap` is the piecewise "add this offset inside these ranges"
+// function from seed-to-location style puzzles, applied to whole sets at once.
+
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, Range, Sub};
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { ranges: Vec::new() }
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add every value in `range`, merging with overlapping or touching ranges
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        // Ranges that overlap or touch `range` form one contiguous run
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+
+        let merged = if first < last {
+            range.start.min(self.ranges[first].start)..range.end.max(self.ranges[last - 1].end)
+        } else {
+            range
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// Remove every value in `range`, splitting ranges that stick out of it
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let first = self.ranges.partition_point(|r| r.end <= range.start);
+        let last = self.ranges.partition_point(|r| r.start < range.end);
+        if first == last {
+            return;
+        }
+
+        let left = self.ranges[first].start..range.start;
+        let right = range.end..self.ranges[last - 1].end;
+        let remainders = [left, right].into_iter().filter(|r| !r.is_empty());
+        self.ranges.splice(first..last, remainders);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(index).is_some_and(|r| r.start <= value)
+    }
+
+    /// Whether every value in `range` is in the set
+    pub fn contains_range(&self, range: Range<T>) -> bool {
+        if range.is_empty() {
+            return true;
+        }
+        let index = self.ranges.partition_point(|r| r.end <= range.start);
+        self.ranges
+            .get(index)
+            .is_some_and(|r| r.start <= range.start && range.end <= r.end)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Total number of values in the set
+    pub fn len(&self) -> T
+    where
+        T: Sub<Output = T> + Sum,
+    {
+        self.ranges.iter().map(|r| r.end - r.start).sum()
+    }
+
+    /// Number of disjoint ranges the set is stored as
+    pub fn range_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// The smallest value in the set
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    /// The disjoint ranges, in increasing order
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = Range<T>> + '_ {
+        self.ranges.iter().cloned()
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.extend(other.iter());
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let overlap = a.start.max(b.start)..a.end.min(b.end);
+            if !overlap.is_empty() {
+                ranges.push(overlap);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        // Pieces of disjoint, non-adjacent inputs can't touch each other
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for range in other.iter() {
+            difference.remove(range);
+        }
+        difference
+    }
+
+    /// Send every value through `map`, splitting ranges at its boundaries
+    pub fn map_offsets(&self, map: &OffsetMap<T>) -> Self
+    where
+        T: Add<Output = T>,
+    {
+        let mut mapped = Self::new();
+        let mut unmapped = self.clone();
+
+        for (source, offset) in &map.pieces {
+            let single = Self::from(source.clone());
+            for overlap in self.intersection(&single).iter() {
+                mapped.insert(overlap.start + *offset..overlap.end + *offset);
+            }
+            unmapped.remove(source.clone());
+        }
+
+        mapped.extend(unmapped.iter());
+        mapped
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        let mut set = Self::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Copy + Ord> Extend<Range<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Range<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(&self.ranges).finish()
+    }
+}
+
+/// A piecewise function adding `offset` to values inside each source range,
+/// and leaving all other values unchanged
+///
+/// ```
+/// use aoc_common::intervals::{IntervalSet, OffsetMap};
+///
+/// // "50 98 2" and "52 50 48" from a seed-to-soil map
+/// let mut map = OffsetMap::new();
+/// map.insert(98..100, 50 - 98);
+/// map.insert(50..98, 52 - 50);
+///
+/// let seeds = IntervalSet::from(79..93);
+/// assert_eq!(seeds.map_offsets(&map), IntervalSet::from(81..95));
+/// assert_eq!(map.apply(99), 51);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OffsetMap<T> {
+    /// Disjoint source ranges, sorted by start
+    pieces: Vec<(Range<T>, T)>,
+}
+
+impl<T> Default for OffsetMap<T> {
+    fn default() -> Self {
+        Self { pieces: Vec::new() }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T>> OffsetMap<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shift values in `source` by `offset`; use a signed `T` for negative
+    /// offsets
+    ///
+    /// Panics if `source` overlaps a range that is already mapped.
+    pub fn insert(&mut self, source: Range<T>, offset: T) {
+        if source.is_empty() {
+            return;
+        }
+        let index = self.pieces.partition_point(|(r, _)| r.end <= source.start);
+        if let Some((next, _)) = self.pieces.get(index) {
+            assert!(
+                source.end <= next.start,
+                "source ranges of an OffsetMap must not overlap"
+            );
+        }
+        self.pieces.insert(index, (source, offset));
+    }
+
+    /// Where a single value ends up
+    pub fn apply(&self, value: T) -> T {
+        let index = self.pieces.partition_point(|(r, _)| r.end <= value);
+        match self.pieces.get(index) {
+            Some((range, offset)) if range.start <= value => value + *offset,
+            _ => value,
+        }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T>> FromIterator<(Range<T>, T)> for OffsetMap<T> {
+    fn from_iter<I: IntoIterator<Item = (Range<T>, T)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (source, offset) in iter {
+            map.insert(source, offset);
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_merges() {
+        let mut set = IntervalSet::new();
+        set.insert(10..20);
+        set.insert(30..40);
+        assert_eq!(set.range_count(), 2);
+
+        // Touching ranges merge too, since [a, b) + [b, c) = [a, c)
+        set.insert(20..25);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [10..25, 30..40]);
+
+        set.insert(5..35);
+        assert_eq!(set, IntervalSet::from(5..40));
+        assert_eq!(set.len(), 35);
+    }
+
+    #[test]
+    fn test_remove_splits() {
+        let mut set = IntervalSet::from(0..100);
+        set.remove(40..60);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0..40, 60..100]);
+
+        set.remove(-5..10);
+        set.remove(90..200);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [10..40, 60..90]);
+        assert_eq!(set.min(), Some(10));
+    }
+
+    #[test]
+    fn test_contains() {
+        let set: IntervalSet<i32> = [0..5, 10..15].into_iter().collect();
+        assert!(set.contains(0));
+        assert!(!set.contains(5));
+        assert!(set.contains(14));
+        assert!(!set.contains(-1));
+
+        assert!(set.contains_range(10..15));
+        assert!(!set.contains_range(4..11));
+        assert!(set.contains_range(7..7));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: IntervalSet<u32> = [0..10, 20..30].into_iter().collect();
+        let b = IntervalSet::from(5..25);
+
+        assert_eq!(a.union(&b), IntervalSet::from(0..30));
+        assert_eq!(
+            a.intersection(&b).iter().collect::<Vec<_>>(),
+            [5..10, 20..25]
+        );
+        assert_eq!(a.difference(&b).iter().collect::<Vec<_>>(), [0..5, 25..30]);
+    }
+
+    #[test]
+    fn test_map_offsets_splits_at_boundaries() {
+        let map: OffsetMap<i64> = [(10..20, 100), (20..25, -20)].into_iter().collect();
+        let set = IntervalSet::from(5..30);
+
+        assert_eq!(
+            set.map_offsets(&map).iter().collect::<Vec<_>>(),
+            [0..10, 25..30, 110..120]
+        );
+        assert_eq!(map.apply(9), 9);
+        assert_eq!(map.apply(10), 110);
+        assert_eq!(map.apply(24), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "must not overlap")]
+    fn test_offset_map_rejects_overlap() {
+        let mut map = OffsetMap::new();
+        map.insert(10..20, 1);
+        map.insert(15..30, 2);
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        Insert(Range<i32>),
+        Remove(Range<i32>),
+    }
+
+    fn range() -> impl Strategy<Value = Range<i32>> {
+        (-20i32..60, 0i32..20).prop_map(|(start, len)| start..start + len)
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![range().prop_map(Op::Insert), range().prop_map(Op::Remove)]
+    }
+
+    fn set_and_model(ops: &[Op]) -> (IntervalSet<i32>, BTreeSet<i32>) {
+        let mut set = IntervalSet::new();
+        let mut model = BTreeSet::new();
+        for op in ops {
+            match op {
+                Op::Insert(r) => {
+                    set.insert(r.clone());
+                    model.extend(r.clone());
+                }
+                Op::Remove(r) => {
+                    set.remove(r.clone());
+                    model.retain(|x| !r.contains(x));
+                }
+            }
+        }
+        (set, model)
+    }
+
+    fn members(set: &IntervalSet<i32>) -> BTreeSet<i32> {
+        set.iter().flatten().collect()
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_model(ops in prop::collection::vec(op(), 0..20)) {
+            let (set, model) = set_and_model(&ops);
+
+            prop_assert_eq!(members(&set), model.clone());
+            prop_assert_eq!(set.len() as usize, model.len());
+            prop_assert_eq!(set.is_empty(), model.is_empty());
+            prop_assert_eq!(set.min(), model.first().copied());
+            for x in -25..85 {
+                prop_assert_eq!(set.contains(x), model.contains(&x));
+            }
+
+            // Normalised: sorted, non-empty, and separated by gaps
+            let ranges: Vec<_> = set.iter().collect();
+            prop_assert!(ranges.iter().all(|r| !r.is_empty()));
+            prop_assert!(ranges.windows(2).all(|w| w[0].end < w[1].start));
+        }
+
+        #[test]
+        fn test_set_operations_match_model(
+            a_ops in prop::collection::vec(op(), 0..10),
+            b_ops in prop::collection::vec(op(), 0..10),
+        ) {
+            let (a, a_model) = set_and_model(&a_ops);
+            let (b, b_model) = set_and_model(&b_ops);
+
+            prop_assert_eq!(members(&a.union(&b)), &a_model | &b_model);
+            prop_assert_eq!(members(&a.intersection(&b)), &a_model & &b_model);
+            prop_assert_eq!(members(&a.difference(&b)), &a_model - &b_model);
+
+            let intersection: Vec<_> = a.intersection(&b).iter().collect();
+            prop_assert!(intersection.windows(2).all(|w| w[0].end < w[1].start));
+        }
+
+        #[test]
+        fn test_map_offsets_matches_pointwise(
+            ops in prop::collection::vec(op(), 0..10),
+            pieces in prop::collection::vec((range(), -30i32..30), 0..5),
+        ) {
+            let (set, model) = set_and_model(&ops);
+            let mut map = OffsetMap::new();
+            let mut covered = IntervalSet::new();
+            for (source, offset) in pieces {
+                if covered.intersection(&IntervalSet::from(source.clone())).is_empty() {
+                    covered.insert(source.clone());
+                    map.insert(source, offset);
+                }
+            }
+
+            let expected: BTreeSet<i32> = model.iter().map(|&x| map.apply(x)).collect();
+            prop_assert_eq!(members(&set.map_offsets(&map)), expected);
+        }
+    }
+}
@@ -4,8 +4,23 @@
 // Keep this minimal - most logic should stay in individual days.
 
 pub mod benchmark;
+pub mod collections;
+pub mod cycle;
+pub mod disjoint_set;
+pub mod graph;
 pub mod grid;
+pub mod input;
+pub mod intervals;
+pub mod memo;
 pub mod nums;
+pub mod parallel;
+pub mod parse;
+pub mod runner;
+pub mod scan;
+pub mod search;
+pub mod vm;
+
+pub use nums::{gcd, lcm};
 
 // Example utilities (add as needed):
 
@@ -15,6 +30,9 @@ pub fn parse_char_grid(input: &str) -> Vec<Vec<char>> {
 }
 
 /// Transpose a grid of chars
+///
+/// Ragged rows are padded with `' '`; `Grid::transpose` and the other
+/// `Grid` symmetries work on rectangular grids.
 pub fn transpose(grid: &[Vec<char>]) -> Vec<Vec<char>> {
     if grid.is_empty() {
         return vec![];
@@ -35,6 +53,8 @@ pub fn transpose(grid: &[Vec<char>]) -> Vec<Vec<char>> {
 }
 
 /// Parse a list of numbers (one per line)
+///
+/// Lines that fail to parse are skipped; `parse::try_lines` reports them instead.
 pub fn parse_numbers<T: std::str::FromStr>(input: &str) -> Vec<T>
 where
     T::Err: std::fmt::Debug,
@@ -45,21 +65,6 @@ where
         .collect()
 }
 
-/// Greatest Common Divisor
-pub fn gcd(mut a: i64, mut b: i64) -> i64 {
-    while b != 0 {
-        let temp = b;
-        b = a % b;
-        a = temp;
-    }
-    a.abs()
-}
-
-/// Least Common Multiple
-pub fn lcm(a: i64, b: i64) -> i64 {
-    (a * b).abs() / gcd(a, b)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
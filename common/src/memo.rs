@@ -0,0 +1,287 @@
+// Memoised recursion without threading a cache through every call
+//
+// `Memo::get` takes the recursive function as a closure whose first argument
+// is "call me again": recursive calls go back through the cache, so the
+// closure reads like the plain recursive definition. The cache's hasher is a
+// type parameter (`FxMemo` for speed with integer keys) and every lookup is
+// counted, so a poor hit rate shows up in `stats()`.
+
+use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+
+use rustc_hash::FxBuildHasher;
+
+/// A memo using FxHash, which is much faster for small keys but not
+/// resistant to adversarial input
+pub type FxMemo<K, V> = Memo<K, V, FxBuildHasher>;
+
+/// How often lookups found a cached value
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl MemoStats {
+    pub fn lookups(&self) -> u64 {
+        self.hits + self.misses
+    }
+
+    /// Fraction of lookups answered from the cache (0 when there were none)
+    pub fn hit_rate(&self) -> f64 {
+        match self.lookups() {
+            0 => 0.0,
+            lookups => self.hits as f64 / lookups as f64,
+        }
+    }
+}
+
+impl fmt::Display for MemoStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} hits / {} lookups ({:.1}%)",
+            self.hits,
+            self.lookups(),
+            self.hit_rate() * 100.0
+        )
+    }
+}
+
+/// Cache for a recursive function from `K` to `V`
+///
+/// ```
+/// use aoc_common::memo::Memo;
+///
+/// let mut memo = Memo::new();
+/// let fib = |fib: &mut dyn FnMut(u64) -> u64, n: u64| {
+///     if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+/// };
+/// assert_eq!(memo.get(90, &fib), 2_880_067_194_370_816_120);
+/// assert_eq!(memo.stats().misses, 91);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Memo<K, V, S = RandomState> {
+    cache: HashMap<K, V, S>,
+    stats: MemoStats,
+}
+
+impl<K, V> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<K, V, S> Memo<K, V, S> {
+    /// Empty memo whose cache uses `hasher`
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            cache: HashMap::with_hasher(hasher),
+            stats: MemoStats::default(),
+        }
+    }
+
+    /// Number of cached values
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        self.stats
+    }
+
+    /// Forget every cached value and reset the statistics
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.stats = MemoStats::default();
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> Memo<K, V, S> {
+    /// The cached value for `key`, without computing or counting anything
+    pub fn cached(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    /// Seed the cache, e.g. with base cases
+    pub fn insert(&mut self, key: K, value: V) {
+        self.cache.insert(key, value);
+    }
+
+    /// The value of `f` at `key`, computing it only on a cache miss
+    ///
+    /// `f` receives a function to call for recursive cases, which goes
+    /// through the same cache. Recursion depth is still limited by the
+    /// stack.
+    pub fn get<F>(&mut self, key: K, f: &F) -> V
+    where
+        K: Clone,
+        V: Clone,
+        F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+    {
+        if let Some(value) = self.cache.get(&key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+        self.stats.misses += 1;
+
+        let value = f(&mut |next| self.get(next, f), key.clone());
+        self.cache.insert(key, value.clone());
+        value
+    }
+}
+
+impl<K, V, S: Default> Default for Memo<K, V, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+/// A recursive function bundled with its cache
+///
+/// ```
+/// use aoc_common::memo::Memoized;
+///
+/// let mut paths = Memoized::new(|paths: &mut dyn FnMut((u32, u32)) -> u64, (x, y)| {
+///     if x == 0 || y == 0 { 1 } else { paths((x - 1, y)) + paths((x, y - 1)) }
+/// });
+/// assert_eq!(paths.get((16, 16)), 601_080_390);
+/// ```
+pub struct Memoized<K, V, F, S = RandomState> {
+    memo: Memo<K, V, S>,
+    f: F,
+}
+
+impl<K, V, F> Memoized<K, V, F>
+where
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    pub fn new(f: F) -> Self {
+        Self {
+            memo: Memo::new(),
+            f,
+        }
+    }
+}
+
+impl<K, V, F, S> Memoized<K, V, F, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+    S: BuildHasher,
+{
+    /// Wrap `f` with a cache that uses `hasher`
+    pub fn with_hasher(f: F, hasher: S) -> Self {
+        Self {
+            memo: Memo::with_hasher(hasher),
+            f,
+        }
+    }
+
+    pub fn get(&mut self, key: K) -> V {
+        self.memo.get(key, &self.f)
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        self.memo.stats()
+    }
+
+    /// The underlying cache
+    pub fn memo(&self) -> &Memo<K, V, S> {
+        &self.memo
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nums::{digit_count, split_digits};
+
+    /// Stones after `blinks` blinks, following the "plutonian pebbles" rules
+    fn stones(memo: &mut FxMemo<(u64, u32), u64>, stone: u64, blinks: u32) -> u64 {
+        memo.get((stone, blinks), &|count, (stone, blinks)| {
+            if blinks == 0 {
+                return 1;
+            }
+            if stone == 0 {
+                count((1, blinks - 1))
+            } else if digit_count(stone) % 2 == 0 {
+                let (left, right) = split_digits(stone, digit_count(stone) / 2);
+                count((left, blinks - 1)) + count((right, blinks - 1))
+            } else {
+                count((stone * 2024, blinks - 1))
+            }
+        })
+    }
+
+    #[test]
+    fn test_stone_counts() {
+        let mut memo = FxMemo::default();
+        let total: u64 = [125, 17].iter().map(|&s| stones(&mut memo, s, 25)).sum();
+        assert_eq!(total, 55312);
+
+        let stats = memo.stats();
+        assert!(stats.hits > 0);
+        assert_eq!(stats.misses as usize, memo.len());
+        assert!(stats.hit_rate() > 0.0 && stats.hit_rate() < 1.0);
+    }
+
+    #[test]
+    fn test_closure_captures_environment() {
+        let towels = ["r", "wr", "b", "g", "bwu", "rb", "gb", "br"];
+        let arrangements = |count: &mut dyn FnMut(&'static str) -> u64, rest: &'static str| {
+            if rest.is_empty() {
+                return 1;
+            }
+            towels
+                .iter()
+                .filter_map(|towel| rest.strip_prefix(towel))
+                .map(count)
+                .sum()
+        };
+
+        let mut memo = Memo::new();
+        assert_eq!(memo.get("brwrr", &arrangements), 2);
+        assert_eq!(memo.get("rrbgbr", &arrangements), 6);
+        assert_eq!(memo.get("ubwu", &arrangements), 0);
+    }
+
+    #[test]
+    fn test_seeded_and_cleared() {
+        let double = |_: &mut dyn FnMut(u32) -> u32, n: u32| n * 2;
+        let mut memo: FxMemo<u32, u32> = FxMemo::default();
+        memo.insert(3, 100);
+        assert_eq!(memo.get(3, &double), 100);
+        assert_eq!(memo.get(4, &double), 8);
+        assert_eq!(memo.cached(&4), Some(&8));
+        assert_eq!(memo.stats(), MemoStats { hits: 1, misses: 1 });
+        assert_eq!(memo.stats().to_string(), "1 hits / 2 lookups (50.0%)");
+
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(memo.stats().lookups(), 0);
+    }
+
+    #[test]
+    fn test_memoized() {
+        let mut collatz = Memoized::with_hasher(
+            |steps: &mut dyn FnMut(u64) -> u32, n: u64| match n {
+                1 => 0,
+                n if n % 2 == 0 => 1 + steps(n / 2),
+                n => 1 + steps(3 * n + 1),
+            },
+            FxBuildHasher,
+        );
+        assert_eq!(collatz.get(27), 111);
+        assert_eq!(collatz.get(54), 112);
+        assert_eq!(collatz.stats().hits, 1);
+        assert!(collatz.memo().cached(&9232).is_some());
+    }
+}
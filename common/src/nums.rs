@@ -1,13 +1,374 @@
-pub fn concat_numbers(left: u64, right: u64) -> u64 {
-    if right == 0 {
-        return left * 10; // Special case: treat 0 as 1 digit
+// Number theory helpers: gcd/lcm, modular arithmetic, CRT, digits and primes
+//
+// Modular functions take `i64` operands and positive moduli, and compute
+// intermediate products in `i128` so they can't overflow. Results are always
+// normalised into `0..m`.
+
+use std::iter::FusedIterator;
+use std::ops::{Add, Div, Mul, Rem};
+
+/// Greatest Common Divisor, always non-negative
+pub fn gcd(mut a: i64, mut b: i64) -> i64 {
+    while b != 0 {
+        let temp = b;
+        b = a % b;
+        a = temp;
+    }
+    a.abs()
+}
+
+/// Least Common Multiple, always non-negative
+///
+/// Divides before multiplying, so it only overflows when the result itself
+/// doesn't fit. `lcm(0, n)` is 0.
+pub fn lcm(a: i64, b: i64) -> i64 {
+    checked_lcm(a, b).expect("lcm overflowed i64")
+}
+
+/// `lcm`, or `None` if the result doesn't fit in an `i64`
+pub fn checked_lcm(a: i64, b: i64) -> Option<i64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)?.checked_abs()
+}
+
+/// Least Common Multiple of every number, 1 for none
+///
+/// ```
+/// use aoc_common::nums::lcm_all;
+///
+/// assert_eq!(lcm_all([4, 6, 10]), 60);
+/// assert_eq!(lcm_all([]), 1);
+/// ```
+pub fn lcm_all(numbers: impl IntoIterator<Item = i64>) -> i64 {
+    checked_lcm_all(numbers).expect("lcm overflowed i64")
+}
+
+/// `lcm_all`, or `None` if the result doesn't fit in an `i64`
+pub fn checked_lcm_all(numbers: impl IntoIterator<Item = i64>) -> Option<i64> {
+    numbers.into_iter().try_fold(1, checked_lcm)
+}
+
+/// Extended Euclid: `(g, x, y)` with `a * x + b * y == g == gcd(a, b)`
+pub fn ext_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The inverse of `a` modulo `m`, if `a` and `m` are coprime
+pub fn mod_inv(a: i64, m: i64) -> Option<i64> {
+    assert!(m > 0, "modulus must be positive");
+    let (g, x, _) = ext_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// `base.pow(exp) % m` by repeated squaring
+pub fn mod_pow(base: i64, mut exp: u64, m: i64) -> i64 {
+    assert!(m > 0, "modulus must be positive");
+    let m = m as i128;
+    let mut base = (base as i128).rem_euclid(m);
+    let mut result = 1 % m;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+
+    result as i64
+}
+
+/// Chinese Remainder Theorem for `x ≡ r (mod m)` congruences
+///
+/// The moduli don't need to be coprime. Returns `(x, lcm of the moduli)`
+/// with `x` the smallest non-negative solution, or `None` if the congruences
+/// contradict each other or the combined modulus doesn't fit in an `i64`.
+///
+/// ```
+/// use aoc_common::nums::crt;
+///
+/// assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// assert_eq!(crt([(1, 4), (3, 6)]), Some((9, 12)));
+/// assert_eq!(crt([(1, 4), (2, 6)]), None);
+/// ```
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    congruences
+        .into_iter()
+        .try_fold((0, 1), |(r1, m1), (r2, m2)| {
+            assert!(m2 > 0, "modulus must be positive");
+            let (g, p, _) = ext_gcd(m1, m2);
+            let diff = r2 as i128 - r1 as i128;
+            if diff % g as i128 != 0 {
+                return None;
+            }
+
+            let step = (m2 / g) as i128;
+            let k = (diff / g as i128 * p as i128).rem_euclid(step);
+            let m = i64::try_from(m1 as i128 * step).ok()?;
+            let x = (r1 as i128 + m1 as i128 * k).rem_euclid(m as i128);
+            Some((x as i64, m))
+        })
+}
+
+/// Floor of the square root
+pub fn isqrt(n: u64) -> u64 {
+    n.isqrt()
+}
+
+/// The square root of `n` if it is a perfect square
+pub fn exact_sqrt(n: u64) -> Option<u64> {
+    let root = n.isqrt();
+    (root * root == n).then_some(root)
+}
+
+/// Decimal digits of `n`, most significant first
+///
+/// Also iterates from the back, for least significant first.
+///
+/// ```
+/// use aoc_common::nums::digits;
+///
+/// assert!(digits(1203).eq([1, 2, 0, 3]));
+/// assert!(digits(1203).rev().eq([3, 0, 2, 1]));
+/// ```
+pub fn digits(n: u64) -> Digits {
+    Digits {
+        rest: n,
+        len: digit_count(n),
+    }
+}
+
+pub struct Digits {
+    /// The digits not yet yielded, as a number
+    rest: u64,
+    len: u32,
+}
+
+impl Iterator for Digits {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        self.len = self.len.checked_sub(1)?;
+        let place = 10u64.pow(self.len);
+        let digit = self.rest / place;
+        self.rest %= place;
+        Some(digit as u8)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len as usize, Some(self.len as usize))
+    }
+}
+
+impl DoubleEndedIterator for Digits {
+    fn next_back(&mut self) -> Option<u8> {
+        self.len = self.len.checked_sub(1)?;
+        let digit = self.rest % 10;
+        self.rest /= 10;
+        Some(digit as u8)
+    }
+}
+
+impl ExactSizeIterator for Digits {}
+
+impl FusedIterator for Digits {}
+
+/// Sieve of Eratosthenes up to and including `limit`
+pub struct Sieve {
+    is_prime: Vec<bool>,
+}
+
+impl Sieve {
+    pub fn new(limit: usize) -> Self {
+        let mut is_prime = vec![true; limit + 1];
+        is_prime[0] = false;
+        if limit >= 1 {
+            is_prime[1] = false;
+        }
+
+        for n in 2..=limit.isqrt() {
+            if is_prime[n] {
+                for multiple in (n * n..=limit).step_by(n) {
+                    is_prime[multiple] = false;
+                }
+            }
+        }
+
+        Self { is_prime }
+    }
+
+    pub fn limit(&self) -> usize {
+        self.is_prime.len() - 1
+    }
+
+    /// Panics if `n` is above the limit
+    pub fn is_prime(&self, n: usize) -> bool {
+        self.is_prime[n]
+    }
+
+    /// The primes up to the limit, in increasing order
+    pub fn primes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.is_prime
+            .iter()
+            .enumerate()
+            .filter_map(|(n, &prime)| prime.then_some(n))
     }
+}
+
+/// All primes up to and including `limit`
+pub fn primes_up_to(limit: usize) -> Vec<usize> {
+    Sieve::new(limit).primes().collect()
+}
+
+/// Unsigned integer types the digit operations work on
+pub trait Unsigned:
+    Copy + Ord + Add<Output = Self> + Mul<Output = Self> + Div<Output = Self> + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const TEN: Self;
 
-    let digits = right.ilog10() + 1; // Count digits in right number
-    left * 10u64.pow(digits) + right
+    /// `10^exp`, or `None` if it doesn't fit
+    fn checked_pow10(exp: u32) -> Option<Self>;
+    fn checked_ilog10(self) -> Option<u32>;
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {
+        $(impl Unsigned for $t {
+            const ZERO: Self = 0;
+            const TEN: Self = 10;
+
+            fn checked_pow10(exp: u32) -> Option<Self> {
+                <$t>::checked_pow(10, exp)
+            }
+
+            fn checked_ilog10(self) -> Option<u32> {
+                <$t>::checked_ilog10(self)
+            }
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+        })*
+    };
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+
+/// Number of decimal digits, counting 0 as one digit
+pub fn digit_count<T: Unsigned>(n: T) -> u32 {
+    n.checked_ilog10().map_or(1, |log| log + 1)
+}
+
+/// The digits of `left` followed by those of `right`, so `concat(12, 345)` is
+/// 12345
+///
+/// Panics on overflow; see `checked_concat`.
+pub fn concat<T: Unsigned>(left: T, right: T) -> T {
+    checked_concat(left, right).expect("concatenated number overflowed")
+}
+
+/// `concat`, or `None` if the result doesn't fit in `T`
+pub fn checked_concat<T: Unsigned>(left: T, right: T) -> Option<T> {
+    // A zero on the left adds no digits, even when 10^digits(right) overflows
+    if left == T::ZERO {
+        return Some(right);
+    }
+    // 0 counts as one digit, so concat(5, 0) is 50
+    left.checked_mul(T::checked_pow10(digit_count(right))?)?
+        .checked_add(right)
+}
+
+/// The inverse of `concat`: what's left of `n` after removing the trailing
+/// digits `suffix`, or `None` if `n` doesn't end with them
+///
+/// Removing every digit leaves 0, so `strip_suffix_digits(345, 345)` is
+/// `Some(0)`.
+///
+/// ```
+/// use aoc_common::nums::strip_suffix_digits;
+///
+/// assert_eq!(strip_suffix_digits(12345u64, 345), Some(12));
+/// assert_eq!(strip_suffix_digits(12345u64, 45), Some(123));
+/// assert_eq!(strip_suffix_digits(12345u64, 44), None);
+/// ```
+pub fn strip_suffix_digits<T: Unsigned>(n: T, suffix: T) -> Option<T> {
+    match T::checked_pow10(digit_count(suffix)) {
+        Some(place) => (n % place == suffix).then(|| n / place),
+        // `suffix` has as many digits as `T` allows, so only itself ends with it
+        None => (n == suffix).then_some(T::ZERO),
+    }
+}
+
+/// Split the digits of `n` after the first `at`, like `str::split_at`
+///
+/// Either side is 0 when it has no digits, and leading zeros of the right
+/// side are dropped, so `split_digits(1005, 2)` is `(10, 5)`.
+///
+/// ```
+/// use aoc_common::nums::split_digits;
+///
+/// assert_eq!(split_digits(253000u64, 3), (253, 0));
+/// assert_eq!(split_digits(1234u32, 0), (0, 1234));
+/// ```
+pub fn split_digits<T: Unsigned>(n: T, at: u32) -> (T, T) {
+    let len = digit_count(n);
+    if at == 0 {
+        return (T::ZERO, n);
+    }
+    if at >= len {
+        return (n, T::ZERO);
+    }
+
+    // 10^(len - at) <= n, so it always fits
+    let place = T::checked_pow10(len - at).unwrap();
+    (n / place, n % place)
+}
+
+/// The digits of `n` in reverse order, or `None` if that doesn't fit in `T`
+///
+/// Trailing zeros become leading zeros and disappear, so 120 reverses to 21.
+pub fn reverse_digits<T: Unsigned>(mut n: T) -> Option<T> {
+    let mut reversed = T::ZERO;
+    while n != T::ZERO {
+        reversed = reversed.checked_mul(T::TEN)?.checked_add(n % T::TEN)?;
+        n = n / T::TEN;
+    }
+    Some(reversed)
+}
+
+/// `concat` for `u64`; panics on overflow
+#[deprecated(note = "use `concat`, or `checked_concat` to handle overflow")]
+pub fn concat_numbers(left: u64, right: u64) -> u64 {
+    concat(left, right)
 }
 
 #[cfg(test)]
+// The first tests predate `concat` and still cover `concat_numbers`
+#[allow(deprecated)]
 mod tests {
     use super::*;
 
@@ -55,6 +416,94 @@ mod tests {
         assert_eq!(concat_numbers(1000, 1), 10001);
     }
 
+    #[test]
+    fn test_lcm_does_not_overflow_early() {
+        // a * b overflows, but the lcm itself fits
+        let a = 3_000_000_000_000;
+        assert_eq!(lcm(a, a * 2), a * 2);
+        assert_eq!(checked_lcm(i64::MAX, i64::MAX - 1), None);
+        assert_eq!(lcm(0, 5), 0);
+        assert_eq!(checked_lcm_all([2, 3, 4, 5, 6]), Some(60));
+    }
+
+    #[test]
+    fn test_ext_gcd() {
+        assert_eq!(ext_gcd(240, 46), (2, -9, 47));
+        assert_eq!(ext_gcd(0, 0), (0, 1, 0));
+        let (g, x, y) = ext_gcd(-12, 18);
+        assert_eq!(g, 6);
+        assert_eq!(-12 * x + 18 * y, 6);
+    }
+
+    #[test]
+    fn test_modular() {
+        assert_eq!(mod_inv(3, 11), Some(4));
+        assert_eq!(mod_inv(-3, 11), Some(7));
+        assert_eq!(mod_inv(4, 8), None);
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(-2, 3, 7), 6);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+        assert_eq!(mod_pow(i64::MAX, u64::MAX, 1_000_000_007), 841_470_462);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([]), Some((0, 1)));
+        assert_eq!(crt([(-1, 5)]), Some((4, 5)));
+        assert_eq!(
+            crt([(0, 7), (12, 13), (55, 59), (25, 31), (12, 19)]),
+            Some((1_068_781, 3_162_341))
+        );
+    }
+
+    #[test]
+    fn test_sqrt_and_digits() {
+        assert_eq!(isqrt(24), 4);
+        assert_eq!(isqrt(25), 5);
+        assert_eq!(exact_sqrt(25), Some(5));
+        assert_eq!(exact_sqrt(26), None);
+        assert_eq!(digit_count(0u64), 1);
+        assert_eq!(digit_count(10u8), 2);
+        assert_eq!(digit_count(u64::MAX), 20);
+        assert!(digits(0).eq([0]));
+        assert_eq!(digits(u64::MAX).len(), 20);
+    }
+
+    #[test]
+    fn test_sieve() {
+        assert_eq!(primes_up_to(30), [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert!(primes_up_to(1).is_empty());
+
+        let sieve = Sieve::new(100);
+        assert_eq!(sieve.limit(), 100);
+        assert!(sieve.is_prime(97));
+        assert!(!sieve.is_prime(91));
+        assert_eq!(sieve.primes().count(), 25);
+    }
+
+    #[test]
+    fn test_generic_digit_ops() {
+        assert_eq!(concat(12u8, 3), 123);
+        assert_eq!(checked_concat(25u8, 6), None);
+        assert_eq!(checked_concat(u64::MAX, 1), None);
+        assert_eq!(checked_concat(1u128, 0), Some(10));
+        assert_eq!(checked_concat(0u8, 100), Some(100));
+
+        assert_eq!(strip_suffix_digits(100u32, 0), Some(10));
+        assert_eq!(strip_suffix_digits(5u32, 5), Some(0));
+        assert_eq!(strip_suffix_digits(3u32, 123), None);
+        assert_eq!(strip_suffix_digits(u8::MAX, 255), Some(0));
+        assert_eq!(strip_suffix_digits(u64::MAX, u64::MAX), Some(0));
+
+        assert_eq!(split_digits(1005u16, 2), (10, 5));
+        assert_eq!(split_digits(7u16, 5), (7, 0));
+
+        assert_eq!(reverse_digits(120u32), Some(21));
+        assert_eq!(reverse_digits(0u32), Some(0));
+        assert_eq!(reverse_digits(199u8), None);
+        assert_eq!(reverse_digits(152u8), Some(251));
+    }
+
     #[test]
     #[should_panic]
     fn test_overflow_behavior() {
@@ -62,14 +511,115 @@ mod tests {
         // This test documents the behavior rather than asserting correctness
         let _ = concat_numbers(u64::MAX, 1);
     }
+
+    #[test]
+    fn test_checked_concat_overflow() {
+        assert_eq!(checked_concat(u64::MAX, 1), None);
+        assert_eq!(
+            checked_concat(1_844_674_407_370_955_161u64, 5),
+            Some(u64::MAX)
+        );
+        assert_eq!(checked_concat(1_844_674_407_370_955_161u64, 6), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflowed")]
+    fn test_concat_panics_on_overflow() {
+        // By design, like concat_numbers; checked_concat is the
+        // non-panicking form
+        let _ = concat(u64::MAX, 1);
+    }
 }
 
 #[cfg(test)]
+// `concat_numbers` keeps its original proptests
+#[allow(deprecated)]
 mod proptests {
     use super::*;
     use proptest::prelude::*;
 
+    fn naive_is_prime(n: usize) -> bool {
+        n >= 2
+            && (2..n)
+                .take_while(|d| d * d <= n)
+                .all(|d| !n.is_multiple_of(d))
+    }
+
     proptest! {
+        #[test]
+        fn test_gcd_divides_and_lcm_is_multiple(a in -100_000i64..100_000, b in 1i64..100_000) {
+            let g = gcd(a, b);
+            prop_assert_eq!(a % g, 0);
+            prop_assert_eq!(b % g, 0);
+
+            let l = lcm(a, b);
+            if a != 0 {
+                prop_assert_eq!(l % a, 0);
+            }
+            prop_assert_eq!(l % b, 0);
+            prop_assert_eq!(l as i128 * g as i128, (a as i128 * b as i128).abs());
+        }
+
+        #[test]
+        fn test_checked_lcm_matches_i128(a in 1i64.., b in 1i64..) {
+            let exact = a as i128 / gcd(a, b) as i128 * b as i128;
+            prop_assert_eq!(checked_lcm(a, b), i64::try_from(exact).ok());
+        }
+
+        #[test]
+        fn test_ext_gcd_bezout(a in -1_000_000_000i64..1_000_000_000, b in -1_000_000_000i64..1_000_000_000) {
+            let (g, x, y) = ext_gcd(a, b);
+            prop_assert_eq!(g, gcd(a, b));
+            prop_assert_eq!(a as i128 * x as i128 + b as i128 * y as i128, g as i128);
+        }
+
+        #[test]
+        fn test_mod_inv(a in any::<i64>(), m in 1i64..1_000_000_007) {
+            match mod_inv(a, m) {
+                Some(inv) => {
+                    prop_assert!((0..m).contains(&inv));
+                    prop_assert_eq!((a as i128 * inv as i128).rem_euclid(m as i128), 1 % m as i128);
+                }
+                None => prop_assert_ne!(gcd(a.rem_euclid(m), m), 1),
+            }
+        }
+
+        #[test]
+        fn test_mod_pow_matches_repeated_multiplication(base in any::<i64>(), exp in 0u64..200, m in 1i64..1_000_000) {
+            let naive = (0..exp).fold(1 % m as i128, |acc, _| acc * base as i128 % m as i128);
+            prop_assert_eq!(mod_pow(base, exp, m) as i128, naive.rem_euclid(m as i128));
+        }
+
+        #[test]
+        fn test_crt_matches_brute_force(congruences in prop::collection::vec((0i64..30, 1i64..30), 1..4)) {
+            let modulus = lcm_all(congruences.iter().map(|&(_, m)| m));
+            let brute = (0..modulus).find(|x| congruences.iter().all(|&(r, m)| x % m == r % m));
+            prop_assert_eq!(crt(congruences.iter().copied()), brute.map(|x| (x, modulus)));
+        }
+
+        #[test]
+        fn test_isqrt(n in any::<u64>()) {
+            let root = isqrt(n) as u128;
+            prop_assert!(root * root <= n as u128);
+            prop_assert!((root + 1) * (root + 1) > n as u128);
+        }
+
+        #[test]
+        fn test_digits_match_to_string(n in any::<u64>()) {
+            let expected: Vec<u8> = n.to_string().bytes().map(|b| b - b'0').collect();
+            prop_assert_eq!(digits(n).collect::<Vec<_>>(), expected.clone());
+            prop_assert_eq!(digits(n).rev().collect::<Vec<_>>(), expected.iter().rev().copied().collect::<Vec<_>>());
+            prop_assert_eq!(digit_count(n) as usize, expected.len());
+        }
+
+        #[test]
+        fn test_sieve_matches_trial_division(limit in 0usize..2000) {
+            let sieve = Sieve::new(limit);
+            for n in 0..=limit {
+                prop_assert_eq!(sieve.is_prime(n), naive_is_prime(n));
+            }
+        }
+
         #[test]
         fn test_concat_is_monotonic(left in 0u64..10000, right in 0u64..10000) {
             // Result should always be >= left (when left != 0)
@@ -85,5 +635,82 @@ mod proptests {
             let expected = format!("{}{}", a, b);
             prop_assert_eq!(result_str, expected);
         }
+
+        #[test]
+        fn test_concat_matches_checked_concat(left in any::<u32>(), right in any::<u32>()) {
+            let expected = format!("{}{}", left, right).parse::<u32>().ok();
+            prop_assert_eq!(checked_concat(left, right), expected);
+            if let Some(expected) = expected {
+                prop_assert_eq!(concat(left, right), expected);
+            }
+        }
     }
 }
+
+/// The digit operations must match their definitions on decimal strings
+#[cfg(test)]
+mod digit_proptests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn from_digits<T: std::str::FromStr>(s: &str) -> Option<T> {
+        if s.is_empty() {
+            "0".parse().ok()
+        } else {
+            s.parse().ok()
+        }
+    }
+
+    macro_rules! digit_proptests {
+        ($($name:ident: $t:ty),*) => {
+            $(mod $name {
+                use super::*;
+
+                proptest! {
+                    #[test]
+                    fn test_digit_count(n in any::<$t>()) {
+                        prop_assert_eq!(digit_count(n) as usize, n.to_string().len());
+                    }
+
+                    #[test]
+                    fn test_checked_concat(left in any::<$t>(), right in any::<$t>()) {
+                        let expected = from_digits::<$t>(&format!("{}{}", left, right));
+                        prop_assert_eq!(checked_concat(left, right), expected);
+                    }
+
+                    #[test]
+                    fn test_strip_suffix_digits(n in any::<$t>(), suffix in any::<$t>()) {
+                        let expected = n
+                            .to_string()
+                            .strip_suffix(&suffix.to_string())
+                            .map(|rest| from_digits::<$t>(rest).unwrap());
+                        prop_assert_eq!(strip_suffix_digits(n, suffix), expected);
+                    }
+
+                    #[test]
+                    fn test_strip_suffix_undoes_concat(left in any::<$t>(), right in any::<$t>()) {
+                        if let Some(n) = checked_concat(left, right) {
+                            prop_assert_eq!(strip_suffix_digits(n, right), Some(left));
+                        }
+                    }
+
+                    #[test]
+                    fn test_split_digits(n in any::<$t>(), at in 0u32..45) {
+                        let s = n.to_string();
+                        let (high, low) = s.split_at((at as usize).min(s.len()));
+                        let expected = (from_digits::<$t>(high).unwrap(), from_digits::<$t>(low).unwrap());
+                        prop_assert_eq!(split_digits(n, at), expected);
+                    }
+
+                    #[test]
+                    fn test_reverse_digits(n in any::<$t>()) {
+                        let reversed: String = n.to_string().chars().rev().collect();
+                        prop_assert_eq!(reverse_digits(n), from_digits::<$t>(&reversed));
+                    }
+                }
+            })*
+        };
+    }
+
+    digit_proptests!(u8_ops: u8, u16_ops: u16, u32_ops: u32, u64_ops: u64, u128_ops: u128, usize_ops: usize);
+}
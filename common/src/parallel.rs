@@ -0,0 +1,168 @@
+// Data-parallel helpers that compile to plain loops without the `parallel` feature
+//
+// Solutions call `par_count`/`par_sum` over a slice of independent
+// candidates. With `--features parallel` the work is spread over rayon's
+// thread pool; without it the same call runs serially, so days don't need
+// any `cfg` of their own. The `_with` variants cut the slice into at most
+// one chunk per thread and hand each chunk a scratch value built by `init`,
+// reused for every item in it; a serial build makes just one.
+
+use std::iter::Sum;
+
+/// Whether this build spreads work over threads
+pub const ENABLED: bool = cfg!(feature = "parallel");
+
+/// Number of threads work is spread over (1 in a serial build)
+pub fn threads() -> usize {
+    #[cfg(feature = "parallel")]
+    {
+        rayon::current_num_threads()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        1
+    }
+}
+
+/// Smallest piece rayon may split `len` items into, so that it makes no
+/// more pieces (and `init` calls) than there are threads
+#[cfg(feature = "parallel")]
+fn chunk_len(len: usize) -> usize {
+    len.div_ceil(threads()).max(1)
+}
+
+/// How many items satisfy `f`
+pub fn par_count<T: Sync>(items: &[T], f: impl Fn(&T) -> bool + Sync + Send) -> usize {
+    par_count_with(items, || (), |_, item| f(item))
+}
+
+/// How many items satisfy `f`, giving each thread its own scratch value
+///
+/// `init` runs at most `threads()` times.
+///
+/// ```
+/// use aoc_common::parallel::par_count_with;
+///
+/// let words = ["level", "rotor", "stone", "kayak"];
+/// let palindromes = par_count_with(&words, Vec::new, |buffer: &mut Vec<char>, word| {
+///     buffer.clear();
+///     buffer.extend(word.chars().rev());
+///     buffer.iter().copied().eq(word.chars())
+/// });
+/// assert_eq!(palindromes, 3);
+/// ```
+pub fn par_count_with<T: Sync, S>(
+    items: &[T],
+    init: impl Fn() -> S + Sync + Send,
+    f: impl Fn(&mut S, &T) -> bool + Sync + Send,
+) -> usize {
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        items
+            .par_iter()
+            .with_min_len(chunk_len(items.len()))
+            .map_init(init, |scratch, item| f(scratch, item))
+            .filter(|&hit| hit)
+            .count()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        let mut scratch = init();
+        items.iter().filter(|item| f(&mut scratch, item)).count()
+    }
+}
+
+/// Sum of `f` over all items
+pub fn par_sum<T: Sync, R>(items: &[T], f: impl Fn(&T) -> R + Sync + Send) -> R
+where
+    R: Sum<R> + Send,
+{
+    par_sum_with(items, || (), |_, item| f(item))
+}
+
+/// Sum of `f` over all items, giving each thread its own scratch value
+///
+/// As with `par_count_with`, `init` runs at most `threads()` times.
+pub fn par_sum_with<T: Sync, S, R>(
+    items: &[T],
+    init: impl Fn() -> S + Sync + Send,
+    f: impl Fn(&mut S, &T) -> R + Sync + Send,
+) -> R
+where
+    R: Sum<R> + Send,
+{
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        items
+            .par_iter()
+            .with_min_len(chunk_len(items.len()))
+            .map_init(init, |scratch, item| f(scratch, item))
+            .sum()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        let mut scratch = init();
+        items.iter().map(|item| f(&mut scratch, item)).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_count_and_sum() {
+        let numbers: Vec<u64> = (1..=1_000).collect();
+        assert_eq!(par_count(&numbers, |n| n.is_multiple_of(7)), 142);
+        assert_eq!(par_sum(&numbers, |&n| n), 500_500);
+        assert_eq!(par_sum(&[] as &[u64], |&n| n), 0);
+    }
+
+    #[test]
+    fn test_scratch_is_reused() {
+        let created = AtomicUsize::new(0);
+        let numbers: Vec<usize> = (0..10_000).collect();
+        let total = par_sum_with(
+            &numbers,
+            || {
+                created.fetch_add(1, Ordering::Relaxed);
+                Vec::with_capacity(4)
+            },
+            |digits: &mut Vec<usize>, &n| {
+                digits.clear();
+                let mut rest = n;
+                while rest > 0 {
+                    digits.push(rest % 10);
+                    rest /= 10;
+                }
+                digits.iter().sum::<usize>()
+            },
+        );
+
+        assert_eq!(total, 180_000);
+        assert!(created.load(Ordering::Relaxed) <= threads());
+        if !ENABLED {
+            assert_eq!(created.load(Ordering::Relaxed), 1);
+            assert_eq!(threads(), 1);
+        }
+    }
+
+    #[test]
+    fn test_scratch_per_thread() {
+        for len in [0, 1, 3, 1_000] {
+            let created = AtomicUsize::new(0);
+            let numbers: Vec<usize> = (0..len).collect();
+            let even = par_count_with(
+                &numbers,
+                || created.fetch_add(1, Ordering::Relaxed),
+                |_, n| n.is_multiple_of(2),
+            );
+
+            assert_eq!(even, len.div_ceil(2));
+            assert!(created.load(Ordering::Relaxed) <= threads());
+        }
+    }
+}
@@ -0,0 +1,473 @@
+// Input parsing without per-number allocations or hand-rolled `split`/`unwrap`
+//
+// `ints` scans bytes for integers and ignores everything else, so most
+// puzzle lines ("7: 6 8 6 15", "47|53", "p=0,4 v=3,-3") need no format
+// knowledge at all. The `try_*` functions and fixed-shape extractors return
+// a `ParseError` naming the line and column of the problem instead of
+// panicking.
+
+use std::fmt;
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+
+/// Integer types `ints` can produce
+pub trait Int: Copy {
+    /// Whether a `-` right before the digits makes the number negative
+    const SIGNED: bool;
+
+    /// Parse ASCII digits, or `None` on overflow
+    fn from_digits(digits: &[u8], negative: bool) -> Option<Self>;
+}
+
+macro_rules! impl_int {
+    ($signed:expr => $($t:ty),*) => {
+        $(impl Int for $t {
+            const SIGNED: bool = $signed;
+
+            fn from_digits(digits: &[u8], negative: bool) -> Option<Self> {
+                // Up to this many digits can't overflow, so skip the checks
+                const SAFE_DIGITS: usize = <$t>::MAX.ilog10() as usize;
+
+                let mut n: Self = 0;
+                if digits.len() <= SAFE_DIGITS {
+                    for &digit in digits {
+                        n = n * 10 + (digit - b'0') as Self;
+                    }
+                    return Some(if negative { 0 - n } else { n });
+                }
+
+                for &digit in digits {
+                    let digit = (digit - b'0') as Self;
+                    n = n.checked_mul(10)?;
+                    // Accumulate negatives downwards so that MIN fits
+                    n = if negative { n.checked_sub(digit)? } else { n.checked_add(digit)? };
+                }
+                Some(n)
+            }
+        })*
+    };
+}
+
+impl_int!(false => u8, u16, u32, u64, u128, usize);
+impl_int!(true => i8, i16, i32, i64, i128, isize);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number, 1 for single-line functions unless set by `try_lines`
+    pub line: usize,
+    /// 1-based byte column within the line
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// A number too large for the target type
+    Overflow { ty: &'static str },
+    /// Something other than a number where one was required
+    NotANumber,
+    /// A fixed-shape extractor found the wrong amount of numbers
+    WrongCount { expected: usize, found: usize },
+    /// `split_pair` did not find its separator
+    MissingSeparator { separator: String },
+    /// A `scan` parser needed `what` here, e.g. "end of input"
+    Expected { what: &'static str },
+    /// A `scan::literal` didn't find its text here
+    ExpectedLiteral { literal: &'static str },
+}
+
+impl ParseError {
+    fn new(column: usize, kind: ParseErrorKind) -> Self {
+        Self {
+            line: 1,
+            column,
+            kind,
+        }
+    }
+
+    /// Move the error to `line`, e.g. after parsing one line of many
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+
+    /// Shift the column, e.g. after parsing part of a line
+    pub fn offset(mut self, columns: usize) -> Self {
+        self.column += columns;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        match &self.kind {
+            ParseErrorKind::Overflow { ty } => write!(f, "number does not fit in {}", ty),
+            ParseErrorKind::NotANumber => write!(f, "expected a number"),
+            ParseErrorKind::WrongCount { expected, found } => {
+                write!(f, "expected {} numbers, found {}", expected, found)
+            }
+            ParseErrorKind::MissingSeparator { separator } => {
+                write!(f, "expected '{}'", separator)
+            }
+            ParseErrorKind::Expected { what } => write!(f, "expected {}", what),
+            ParseErrorKind::ExpectedLiteral { literal } => write!(f, "expected '{}'", literal),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Every integer in `s`, in order
+///
+/// For signed types a `-` directly before the digits is a minus sign, so
+/// `"1-3"` gives `1, 3` as `u32` but `1, -3` as `i32`. Panics if a number
+/// overflows `T`; use `try_ints` to get an error instead.
+///
+/// ```
+/// use aoc_common::parse::ints;
+///
+/// let numbers: Vec<i32> = ints("p=0,4 v=3,-3").collect();
+/// assert_eq!(numbers, [0, 4, 3, -3]);
+/// ```
+pub fn ints<T: Int>(s: &str) -> Ints<'_, T> {
+    Ints {
+        bytes: s.as_bytes(),
+        pos: 0,
+        _marker: PhantomData,
+    }
+}
+
+/// Like `ints`, but yields an error for numbers that overflow `T`
+pub fn try_ints<T: Int>(s: &str) -> TryInts<'_, T> {
+    TryInts(ints(s))
+}
+
+pub struct Ints<'a, T> {
+    bytes: &'a [u8],
+    pos: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Int> Ints<'_, T> {
+    /// The next number with the column it starts at
+    fn next_with_column(&mut self) -> Option<(usize, Option<T>)> {
+        let bytes = self.bytes;
+        let mut start = self.pos;
+        while start < bytes.len() && !bytes[start].is_ascii_digit() {
+            start += 1;
+        }
+        if start == bytes.len() {
+            self.pos = start;
+            return None;
+        }
+
+        let mut end = start + 1;
+        while end < bytes.len() && bytes[end].is_ascii_digit() {
+            end += 1;
+        }
+        self.pos = end;
+
+        let negative = T::SIGNED && start > 0 && bytes[start - 1] == b'-';
+        let column = if negative { start } else { start + 1 };
+        Some((column, T::from_digits(&bytes[start..end], negative)))
+    }
+}
+
+impl<T: Int> Iterator for Ints<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let (column, value) = self.next_with_column()?;
+        Some(value.unwrap_or_else(|| {
+            panic!(
+                "column {}: number does not fit in {}",
+                column,
+                std::any::type_name::<T>()
+            )
+        }))
+    }
+}
+
+impl<T: Int> FusedIterator for Ints<'_, T> {}
+
+pub struct TryInts<'a, T>(Ints<'a, T>);
+
+impl<T: Int> Iterator for TryInts<'_, T> {
+    type Item = Result<T, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (column, value) = self.0.next_with_column()?;
+        Some(value.ok_or_else(|| {
+            ParseError::new(
+                column,
+                ParseErrorKind::Overflow {
+                    ty: std::any::type_name::<T>(),
+                },
+            )
+        }))
+    }
+}
+
+impl<T: Int> FusedIterator for TryInts<'_, T> {}
+
+/// Parse all of `s` (apart from surrounding whitespace) as one integer
+pub fn int<T: Int>(s: &str) -> Result<T, ParseError> {
+    let leading = s.len() - s.trim_start().len();
+    let bytes = s.trim().as_bytes();
+    let negative = T::SIGNED && bytes.first() == Some(&b'-');
+    let digits = &bytes[negative as usize..];
+
+    if let Some(bad) = digits.iter().position(|b| !b.is_ascii_digit()) {
+        let column = leading + negative as usize + bad + 1;
+        return Err(ParseError::new(column, ParseErrorKind::NotANumber));
+    }
+    if digits.is_empty() {
+        return Err(ParseError::new(leading + 1, ParseErrorKind::NotANumber));
+    }
+
+    T::from_digits(digits, negative).ok_or_else(|| {
+        ParseError::new(
+            leading + 1,
+            ParseErrorKind::Overflow {
+                ty: std::any::type_name::<T>(),
+            },
+        )
+    })
+}
+
+/// Exactly `N` integers from `s`
+///
+/// ```
+/// use aoc_common::parse::array;
+///
+/// assert_eq!(array::<u32, 5>("#1 @ 1,3: 4x4"), Ok([1, 1, 3, 4, 4]));
+/// assert!(array::<u32, 2>("1,2,3").is_err());
+/// ```
+pub fn array<T: Int, const N: usize>(s: &str) -> Result<[T; N], ParseError> {
+    let mut values = [None; N];
+    let mut found = 0;
+
+    for value in try_ints(s) {
+        if let Some(slot) = values.get_mut(found) {
+            *slot = Some(value?);
+        }
+        found += 1;
+    }
+
+    if found != N {
+        return Err(ParseError::new(
+            s.len() + 1,
+            ParseErrorKind::WrongCount { expected: N, found },
+        ));
+    }
+    Ok(values.map(|value| value.unwrap()))
+}
+
+/// Exactly two integers from `s`, e.g. `"47|53"`
+pub fn pair<T: Int>(s: &str) -> Result<(T, T), ParseError> {
+    let [a, b] = array(s)?;
+    Ok((a, b))
+}
+
+/// Exactly three integers from `s`, e.g. `"2x3x4"`
+pub fn triple<T: Int>(s: &str) -> Result<(T, T, T), ParseError> {
+    let [a, b, c] = array(s)?;
+    Ok((a, b, c))
+}
+
+/// Split `s` at the first `separator`, e.g. `"190: 10 19"` at `": "`
+pub fn split_pair<'a>(s: &'a str, separator: &str) -> Result<(&'a str, &'a str), ParseError> {
+    s.split_once(separator).ok_or_else(|| {
+        ParseError::new(
+            s.len() + 1,
+            ParseErrorKind::MissingSeparator {
+                separator: separator.to_string(),
+            },
+        )
+    })
+}
+
+/// Parse every line with `f`, stopping at the first error with its line number
+///
+/// ```
+/// use aoc_common::parse::{pair, try_lines};
+///
+/// let rules: Vec<(u32, u32)> = try_lines("47|53\n97|13", pair).unwrap();
+/// assert_eq!(rules, [(47, 53), (97, 13)]);
+///
+/// let err = try_lines::<(u32, u32)>("47|53\n97", pair).unwrap_err();
+/// assert_eq!(err.to_string(), "line 2, column 3: expected 2 numbers, found 1");
+/// ```
+pub fn try_lines<T>(
+    input: &str,
+    mut f: impl FnMut(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| f(line).map_err(|e| e.at_line(index + 1)))
+        .collect()
+}
+
+/// Sections of the input separated by blank lines
+///
+/// Any number of blank (or whitespace-only) lines separates two blocks, and
+/// leading or trailing blank lines produce no empty blocks. Each block keeps
+/// its inner newlines but not the trailing one.
+pub fn split_blocks(input: &str) -> SplitBlocks<'_> {
+    SplitBlocks { rest: input }
+}
+
+pub struct SplitBlocks<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for SplitBlocks<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let mut start = None;
+        let mut end = 0;
+        let mut offset = 0;
+
+        for line in self.rest.split_inclusive('\n') {
+            let blank = line.trim().is_empty();
+            match (blank, start) {
+                (false, None) => start = Some(offset),
+                (true, Some(_)) => break,
+                _ => {}
+            }
+            offset += line.len();
+            if !blank {
+                end = offset - line.len() + line.trim_end_matches(['\n', '\r']).len();
+            }
+        }
+
+        let block = &self.rest[start?..end];
+        self.rest = &self.rest[offset..];
+        Some(block)
+    }
+}
+
+impl FusedIterator for SplitBlocks<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ints_unsigned_and_signed() {
+        let unsigned: Vec<u32> = ints("1-3 a:-12,b+4").collect();
+        assert_eq!(unsigned, [1, 3, 12, 4]);
+
+        let signed: Vec<i32> = ints("1-3 a:-12,b+4").collect();
+        assert_eq!(signed, [1, -3, -12, 4]);
+
+        assert_eq!(ints::<u8>("no numbers here").next(), None);
+    }
+
+    #[test]
+    fn test_ints_extremes() {
+        let values: Vec<i64> = ints("-9223372036854775808 9223372036854775807").collect();
+        assert_eq!(values, [i64::MIN, i64::MAX]);
+        assert_eq!(ints::<u8>("255").collect::<Vec<_>>(), [255]);
+    }
+
+    #[test]
+    #[should_panic(expected = "column 5: number does not fit in u8")]
+    fn test_ints_panics_on_overflow() {
+        ints::<u8>("1 2 256").for_each(drop);
+    }
+
+    #[test]
+    fn test_try_ints_reports_overflow() {
+        let results: Vec<_> = try_ints::<i8>("x=127 y=-129").collect();
+        assert_eq!(results[0], Ok(127));
+        assert_eq!(
+            results[1],
+            Err(ParseError {
+                line: 1,
+                column: 9,
+                kind: ParseErrorKind::Overflow { ty: "i8" },
+            })
+        );
+    }
+
+    #[test]
+    fn test_int() {
+        assert_eq!(int::<i32>(" -42\n"), Ok(-42));
+        assert_eq!(int::<u32>("190"), Ok(190));
+        assert_eq!(
+            int::<u32>("19x").unwrap_err().to_string(),
+            "line 1, column 3: expected a number"
+        );
+        assert_eq!(
+            int::<u32>("-1").unwrap_err().kind,
+            ParseErrorKind::NotANumber
+        );
+        assert_eq!(int::<u32>("").unwrap_err().kind, ParseErrorKind::NotANumber);
+    }
+
+    #[test]
+    fn test_fixed_shapes() {
+        assert_eq!(pair::<u32>("47|53"), Ok((47, 53)));
+        assert_eq!(triple::<u32>("2x3x4"), Ok((2, 3, 4)));
+        assert_eq!(
+            pair::<u32>("1,2,3").unwrap_err().kind,
+            ParseErrorKind::WrongCount {
+                expected: 2,
+                found: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_split_pair() {
+        assert_eq!(split_pair("190: 10 19", ": "), Ok(("190", "10 19")));
+        assert_eq!(
+            split_pair("190 10 19", ": ").unwrap_err().to_string(),
+            "line 1, column 10: expected ': '"
+        );
+    }
+
+    #[test]
+    fn test_try_lines_names_the_line() {
+        let err = try_lines("1\n2\nthree", int::<u32>).unwrap_err();
+        assert_eq!(err.line, 3);
+        assert_eq!(err.column, 1);
+    }
+
+    #[test]
+    fn test_split_blocks() {
+        let blocks: Vec<_> = split_blocks("a\nb\n\nc\n").collect();
+        assert_eq!(blocks, ["a\nb", "c"]);
+
+        let blocks: Vec<_> = split_blocks("\n\na\r\n\r\n  \n\nb\r\nc\r\n\n").collect();
+        assert_eq!(blocks, ["a", "b\r\nc"]);
+
+        assert_eq!(split_blocks("").next(), None);
+        assert_eq!(split_blocks("\n \n").next(), None);
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_ints_matches_std(values in prop::collection::vec(any::<i64>(), 0..20), sep in "[ ,;:|a-z]{1,3}") {
+            let line = values.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(&sep);
+            let parsed: Vec<i64> = ints(&line).collect();
+            prop_assert_eq!(parsed, values);
+        }
+
+        #[test]
+        fn test_int_matches_std(s in "-?[0-9]{1,25}") {
+            prop_assert_eq!(int::<i64>(&s).ok(), s.parse::<i64>().ok());
+            prop_assert_eq!(int::<u32>(&s).ok(), s.parse::<u32>().ok());
+        }
+    }
+}
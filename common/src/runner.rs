@@ -0,0 +1,367 @@
+// Shared `main` for day binaries: input loading, timing and output
+//
+// Day binaries call `aoc_common::aoc_main!("NN");` instead of writing `main`, and get:
+//
+//   --part <1|2>     run only one part
+//   --input <path>   read another input file (`-` for stdin)
+//   --repeat <N>     run each part N times and report the average time
+//   --json           print one JSON object instead of text
+//
+// Both outputs say whether the build spreads work over threads (the
+// `parallel` feature), so timings from serial and parallel builds aren't
+// compared by accident.
+
+use std::fmt::{self, Display, Write};
+use std::time::{Duration, Instant};
+
+use crate::input::{self, Source};
+use crate::parallel;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    pub part: Option<u8>,
+    pub input: Source,
+    pub repeat: u32,
+    pub json: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            part: None,
+            input: Source::Default,
+            repeat: 1,
+            json: false,
+        }
+    }
+}
+
+const USAGE: &str = "Usage: dayNN [--part <1|2>] [--input <path|->] [--repeat <N>] [--json]";
+
+impl Options {
+    pub fn from_args<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut options = Options::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(value.to_string()))
+                }
+                _ => (arg, None),
+            };
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("{} needs a value", flag))
+            };
+
+            match flag.as_str() {
+                "--part" | "-p" => {
+                    options.part = match value()?.as_str() {
+                        "1" => Some(1),
+                        "2" => Some(2),
+                        other => return Err(format!("Invalid part '{}', expected 1 or 2", other)),
+                    }
+                }
+                "--input" | "-i" => options.input = Source::parse(&value()?),
+                "--repeat" | "-r" => {
+                    options.repeat = value()?
+                        .parse()
+                        .ok()
+                        .filter(|&n| n > 0)
+                        .ok_or("--repeat needs a positive number")?
+                }
+                "--json" => options.json = true,
+                other => return Err(format!("Unknown argument '{}'", other)),
+            }
+        }
+
+        Ok(options)
+    }
+
+    fn runs(&self, part: u8) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+}
+
+/// Outcome of running one part
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: String,
+    /// Average over all repeats
+    pub time: Duration,
+}
+
+/// Run a solver `repeat` times, returning the first answer and the average time
+pub fn time_part<T: Display>(
+    part: u8,
+    input: &str,
+    repeat: u32,
+    solve: impl Fn(&str) -> T,
+) -> PartResult {
+    let mut answer = None;
+    let mut total = Duration::ZERO;
+
+    for _ in 0..repeat.max(1) {
+        let start = Instant::now();
+        let result = solve(input);
+        total += start.elapsed();
+        answer.get_or_insert_with(|| result.to_string());
+    }
+
+    PartResult {
+        part,
+        answer: answer.unwrap_or_default(),
+        time: total / repeat.max(1),
+    }
+}
+
+/// Run the selected parts of a day and print the results
+pub fn run<A: Display, B: Display>(
+    manifest_dir: &str,
+    day: &str,
+    part1: impl Fn(&str) -> A,
+    part2: impl Fn(&str) -> B,
+) {
+    let options = Options::from_args(std::env::args().skip(1)).unwrap_or_else(|e| {
+        eprintln!("Error: {}\n{}", e, USAGE);
+        std::process::exit(2);
+    });
+
+    let input = input::read_or_exit(manifest_dir, day, &options.input);
+
+    let mut results = Vec::new();
+    if options.runs(1) {
+        results.push(time_part(1, &input, options.repeat, part1));
+    }
+    if options.runs(2) {
+        results.push(time_part(2, &input, options.repeat, part2));
+    }
+
+    let threads = parallel::ENABLED.then(parallel::threads);
+    if options.json {
+        println!("{}", to_json(day, &options, &results, threads));
+    } else {
+        print!("{}", to_text(&options, &results, threads));
+    }
+}
+
+/// Format a duration with a unit that keeps it readable (ns, µs, ms or s)
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.2}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
+
+/// `threads` is `Some` for a parallel build
+fn to_text(options: &Options, results: &[PartResult], threads: Option<usize>) -> String {
+    let mut out = String::new();
+    let suffix = if options.repeat > 1 {
+        format!(", avg of {} runs", options.repeat)
+    } else {
+        String::new()
+    };
+
+    for result in results {
+        let _ = writeln!(
+            out,
+            "Part {}: {} ({}{})",
+            result.part,
+            result.answer,
+            format_duration(result.time),
+            suffix
+        );
+    }
+
+    if results.len() > 1 {
+        let total: Duration = results.iter().map(|r| r.time).sum();
+        let _ = writeln!(out, "\nTotal time: {}", format_duration(total));
+    }
+    if let Some(threads) = threads {
+        let _ = writeln!(out, "Parallel build ({} threads)", threads);
+    }
+
+    out
+}
+
+/// Stable machine-readable output, one object per run:
+///
+/// `{"day":1,"repeat":1,"parts":[{"part":1,"answer":"11","time_ns":1234}],"total_ns":1234,"parallel":false,"threads":1}`
+fn to_json(day: &str, options: &Options, results: &[PartResult], threads: Option<usize>) -> String {
+    let day: u32 = day.parse().unwrap_or(0);
+    let parts: Vec<String> = results
+        .iter()
+        .map(|r| {
+            format!(
+                "{{\"part\":{},\"answer\":{},\"time_ns\":{}}}",
+                r.part,
+                JsonString(&r.answer),
+                r.time.as_nanos()
+            )
+        })
+        .collect();
+    let total: Duration = results.iter().map(|r| r.time).sum();
+
+    format!(
+        "{{\"day\":{},\"repeat\":{},\"parts\":[{}],\"total_ns\":{},\"parallel\":{},\"threads\":{}}}",
+        day,
+        options.repeat,
+        parts.join(","),
+        total.as_nanos(),
+        threads.is_some(),
+        threads.unwrap_or(1)
+    )
+}
+
+/// Quote and escape a string for JSON output
+struct JsonString<'a>(&'a str);
+
+impl Display for JsonString<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('"')?;
+        for ch in self.0.chars() {
+            match ch {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str("\\n")?,
+                '\r' => f.write_str("\\r")?,
+                '\t' => f.write_str("\\t")?,
+                c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+                c => f.write_char(c)?,
+            }
+        }
+        f.write_char('"')
+    }
+}
+
+/// Generate `main` for a day binary
+///
+/// ```rust,ignore
+/// aoc_common::aoc_main!("01");                  // uses `part1` and `part2`
+/// aoc_common::aoc_main!("01", solve1, solve2);  // custom solver names
+/// ```
+#[macro_export]
+macro_rules! aoc_main {
+    ($day:expr) => {
+        $crate::aoc_main!($day, part1, part2);
+    };
+    ($day:expr, $part1:path, $part2:path) => {
+        fn main() {
+            $crate::runner::run(env!("CARGO_MANIFEST_DIR"), $day, $part1, $part2);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_options_from_args() {
+        assert_eq!(Options::from_args(args(&[])).unwrap(), Options::default());
+
+        let options =
+            Options::from_args(args(&["--part", "2", "--repeat=10", "--json", "-i", "-"])).unwrap();
+        assert_eq!(
+            options,
+            Options {
+                part: Some(2),
+                input: Source::Stdin,
+                repeat: 10,
+                json: true,
+            }
+        );
+
+        for input in [&["--input", "x.txt"][..], &["--input=x.txt"]] {
+            assert_eq!(
+                Options::from_args(args(input)).unwrap().input,
+                Source::File("x.txt".into())
+            );
+        }
+    }
+
+    #[test]
+    fn test_options_errors() {
+        assert!(Options::from_args(args(&["--part", "3"])).is_err());
+        assert!(Options::from_args(args(&["--repeat", "0"])).is_err());
+        assert!(Options::from_args(args(&["--repeat"])).is_err());
+        assert!(Options::from_args(args(&["--bogus"])).is_err());
+        assert!(Options::from_args(args(&["--input"])).is_err());
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999ns");
+        assert_eq!(format_duration(Duration::from_nanos(26_458)), "26.46µs");
+        assert_eq!(format_duration(Duration::from_micros(1_500)), "1.50ms");
+        assert_eq!(format_duration(Duration::from_millis(2_345)), "2.35s");
+    }
+
+    #[test]
+    fn test_time_part_uses_first_answer() {
+        let result = time_part(1, "1 2 3", 5, |input| input.split(' ').count());
+        assert_eq!(result.part, 1);
+        assert_eq!(result.answer, "3");
+    }
+
+    #[test]
+    fn test_json_output() {
+        let results = [
+            PartResult {
+                part: 1,
+                answer: "11".to_string(),
+                time: Duration::from_nanos(1_200),
+            },
+            PartResult {
+                part: 2,
+                answer: "a \"quoted\"\nanswer".to_string(),
+                time: Duration::from_nanos(800),
+            },
+        ];
+
+        assert_eq!(
+            to_json("01", &Options::default(), &results, None),
+            r#"{"day":1,"repeat":1,"parts":[{"part":1,"answer":"11","time_ns":1200},{"part":2,"answer":"a \"quoted\"\nanswer","time_ns":800}],"total_ns":2000,"parallel":false,"threads":1}"#
+        );
+        assert!(
+            to_json("01", &Options::default(), &results, Some(8))
+                .ends_with(r#""total_ns":2000,"parallel":true,"threads":8}"#)
+        );
+    }
+
+    #[test]
+    fn test_text_output() {
+        let results = [PartResult {
+            part: 2,
+            answer: "31".to_string(),
+            time: Duration::from_micros(12),
+        }];
+        let options = Options {
+            repeat: 3,
+            ..Options::default()
+        };
+
+        assert_eq!(
+            to_text(&options, &results, None),
+            "Part 2: 31 (12.00µs, avg of 3 runs)\n"
+        );
+        assert_eq!(
+            to_text(&options, &results, Some(4)),
+            "Part 2: 31 (12.00µs, avg of 3 runs)\nParallel build (4 threads)\n"
+        );
+    }
+}
@@ -0,0 +1,409 @@
+// Parser combinators for small puzzle grammars, without regex
+//
+// A parser is anything implementing `Parser`: it takes the remaining input
+// and returns a value plus what is left, or a `Failure`. Plain closures
+// `Fn(&str) -> PResult<T>` are parsers, and the building blocks here
+// (`literal`, `uint`, `int`, `alt`, `many`, `sep_by`) combine into bigger
+// ones. Nothing allocates except `many` and `sep_by`'s result vectors.
+//
+// Failures only record how much input was left, so they are cheap to create
+// and throw away while `alt` and `find_all` try alternatives. `parse_all`
+// turns the final failure into a `ParseError` with a line and column.
+
+use std::marker::PhantomData;
+
+use crate::parse::{Int, ParseError, ParseErrorKind};
+
+/// A parsed value and the input after it, or why parsing failed
+pub type PResult<'a, T> = Result<(T, &'a str), Failure>;
+
+/// Where and why a parser failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    /// Bytes of input left at the point of failure
+    remaining: usize,
+    kind: ParseErrorKind,
+}
+
+impl Failure {
+    fn at(rest: &str, kind: ParseErrorKind) -> Self {
+        Self {
+            remaining: rest.len(),
+            kind,
+        }
+    }
+
+    fn expected(rest: &str, what: &'static str) -> Self {
+        Self::at(rest, ParseErrorKind::Expected { what })
+    }
+
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+
+    /// Locate the failure within `input`, the string parsing started from
+    pub fn into_error(self, input: &str) -> ParseError {
+        let offset = input.len().saturating_sub(self.remaining);
+        let before = &input.as_bytes()[..offset];
+        let line_start = before
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+        ParseError {
+            line: before.iter().filter(|&&b| b == b'\n').count() + 1,
+            column: offset - line_start + 1,
+            kind: self.kind,
+        }
+    }
+}
+
+/// Something that parses a `T` from the front of a string
+pub trait Parser<'a, T> {
+    fn parse(&self, input: &'a str) -> PResult<'a, T>;
+
+    /// Convert the parsed value
+    fn map<U>(self, f: impl Fn(T) -> U) -> impl Parser<'a, U>
+    where
+        Self: Sized,
+    {
+        move |input| {
+            let (value, rest) = self.parse(input)?;
+            Ok((f(value), rest))
+        }
+    }
+
+    /// Parse `self` then `next`, keeping both values
+    fn then<U>(self, next: impl Parser<'a, U>) -> impl Parser<'a, (T, U)>
+    where
+        Self: Sized,
+    {
+        move |input| {
+            let (a, rest) = self.parse(input)?;
+            let (b, rest) = next.parse(rest)?;
+            Ok(((a, b), rest))
+        }
+    }
+
+    /// Parse `self` then `next`, keeping only the value of `self`
+    fn skip<U>(self, next: impl Parser<'a, U>) -> impl Parser<'a, T>
+    where
+        Self: Sized,
+    {
+        move |input| {
+            let (value, rest) = self.parse(input)?;
+            let (_, rest) = next.parse(rest)?;
+            Ok((value, rest))
+        }
+    }
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: Fn(&'a str) -> PResult<'a, T>,
+{
+    fn parse(&self, input: &'a str) -> PResult<'a, T> {
+        self(input)
+    }
+}
+
+/// Exactly `lit`
+pub fn literal<'a>(lit: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| match input.strip_prefix(lit) {
+        Some(rest) => Ok((&input[..lit.len()], rest)),
+        None => Err(Failure::at(
+            input,
+            ParseErrorKind::ExpectedLiteral { literal: lit },
+        )),
+    }
+}
+
+/// Digits without a sign
+pub fn uint<'a, T: Int>() -> impl Parser<'a, T> {
+    move |input: &'a str| digits(input, false)
+}
+
+/// Digits with an optional leading `-` (for signed `T`) or `+`
+pub fn int<'a, T: Int>() -> impl Parser<'a, T> {
+    move |input: &'a str| match input.as_bytes().first() {
+        Some(b'-') if T::SIGNED => digits(&input[1..], true),
+        Some(b'+') => digits(&input[1..], false),
+        _ => digits(input, false),
+    }
+}
+
+fn digits<T: Int>(input: &str, negative: bool) -> PResult<'_, T> {
+    let len = input.bytes().take_while(u8::is_ascii_digit).count();
+    if len == 0 {
+        return Err(Failure::at(input, ParseErrorKind::NotANumber));
+    }
+    let (number, rest) = input.split_at(len);
+    match T::from_digits(number.as_bytes(), negative) {
+        Some(n) => Ok((n, rest)),
+        None => Err(Failure::at(
+            input,
+            ParseErrorKind::Overflow {
+                ty: std::any::type_name::<T>(),
+            },
+        )),
+    }
+}
+
+/// Any run of spaces and tabs, possibly empty
+pub fn spaces<'a>() -> impl Parser<'a, &'a str> {
+    move |input: &'a str| {
+        let rest = input.trim_start_matches([' ', '\t']);
+        Ok((&input[..input.len() - rest.len()], rest))
+    }
+}
+
+/// `p` after `prefix`, keeping only the value of `p`
+pub fn preceded<'a, A, T>(prefix: impl Parser<'a, A>, p: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |input| {
+        let (_, rest) = prefix.parse(input)?;
+        p.parse(rest)
+    }
+}
+
+/// `p` between `open` and `close`, keeping only the value of `p`
+pub fn delimited<'a, A, T, B>(
+    open: impl Parser<'a, A>,
+    p: impl Parser<'a, T>,
+    close: impl Parser<'a, B>,
+) -> impl Parser<'a, T> {
+    preceded(open, p.skip(close))
+}
+
+/// A tuple of parsers with the same output, tried in order by `alt`
+pub trait Alt<'a, T> {
+    fn choice(&self, input: &'a str) -> PResult<'a, T>;
+}
+
+macro_rules! impl_alt {
+    ($($p:ident $i:tt),+) => {
+        impl<'a, T, $($p: Parser<'a, T>),+> Alt<'a, T> for ($($p,)+) {
+            fn choice(&self, input: &'a str) -> PResult<'a, T> {
+                // Report the failure that got furthest (the first on ties),
+                // which is usually the alternative the input was meant to be
+                let mut best: Option<Failure> = None;
+                $(
+                    match self.$i.parse(input) {
+                        Ok(done) => return Ok(done),
+                        Err(failure) => {
+                            if best.as_ref().is_none_or(|b| failure.remaining < b.remaining) {
+                                best = Some(failure);
+                            }
+                        }
+                    }
+                )+
+                Err(best.unwrap())
+            }
+        }
+    };
+}
+
+impl_alt!(A 0, B 1);
+impl_alt!(A 0, B 1, C 2);
+impl_alt!(A 0, B 1, C 2, D 3);
+impl_alt!(A 0, B 1, C 2, D 3, E 4);
+impl_alt!(A 0, B 1, C 2, D 3, E 4, F 5);
+
+/// The first of the parsers in `alternatives` (a tuple) that succeeds
+pub fn alt<'a, T>(alternatives: impl Alt<'a, T>) -> impl Parser<'a, T> {
+    move |input| alternatives.choice(input)
+}
+
+/// `p` repeated as many times as it matches, possibly zero
+pub fn many<'a, T>(p: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |mut input: &'a str| {
+        let mut values = Vec::new();
+        // Stop on failure, or on a match that consumed nothing (it would
+        // match forever)
+        while let Ok((value, rest)) = p.parse(input) {
+            values.push(value);
+            if rest.len() == input.len() {
+                break;
+            }
+            input = rest;
+        }
+        Ok((values, input))
+    }
+}
+
+/// Zero or more `p` separated by `sep`
+///
+/// A trailing separator is left unconsumed.
+pub fn sep_by<'a, T, S>(p: impl Parser<'a, T>, sep: impl Parser<'a, S>) -> impl Parser<'a, Vec<T>> {
+    move |input: &'a str| {
+        let mut values = Vec::new();
+        let Ok((first, mut input)) = p.parse(input) else {
+            return Ok((values, input));
+        };
+        values.push(first);
+
+        while let Ok((_, after_sep)) = sep.parse(input) {
+            match p.parse(after_sep) {
+                Ok((value, rest)) => {
+                    values.push(value);
+                    input = rest;
+                }
+                Err(_) => break,
+            }
+        }
+        Ok((values, input))
+    }
+}
+
+/// Run `p` on the whole of `input`, failing if anything is left over
+///
+/// ```
+/// use aoc_common::scan::{int, literal, parse_all, sep_by, Parser};
+///
+/// let list = sep_by(int::<i32>(), literal(", "));
+/// assert_eq!(parse_all(&list, "3, -1, 4"), Ok(vec![3, -1, 4]));
+///
+/// let error = parse_all(&list, "3, -1; 4").unwrap_err();
+/// assert_eq!(error.to_string(), "line 1, column 6: expected end of input");
+/// ```
+pub fn parse_all<'a, T>(p: &impl Parser<'a, T>, input: &'a str) -> Result<T, ParseError> {
+    match p.parse(input) {
+        Ok((value, "")) => Ok(value),
+        Ok((_, rest)) => Err(Failure::expected(rest, "end of input").into_error(input)),
+        Err(failure) => Err(failure.into_error(input)),
+    }
+}
+
+/// Every non-overlapping match of `p` in `input`, skipping anything between
+///
+/// This is the regex `find_iter` pattern: at each position try `p`, and on
+/// failure move one character along.
+///
+/// ```
+/// use aoc_common::scan::{find_all, literal, uint, delimited, Parser};
+///
+/// let pair = uint::<u32>().skip(literal(",")).then(uint::<u32>());
+/// let mul = delimited(literal("mul("), pair, literal(")"));
+/// let products: Vec<u32> = find_all(mul, "xmul(2,4)%mul[3,7]!mul(5,5)")
+///     .map(|(a, b)| a * b)
+///     .collect();
+/// assert_eq!(products, [8, 25]);
+/// ```
+pub fn find_all<'a, T, P: Parser<'a, T>>(p: P, input: &'a str) -> FindAll<'a, T, P> {
+    FindAll {
+        parser: p,
+        rest: input,
+        _marker: PhantomData,
+    }
+}
+
+pub struct FindAll<'a, T, P> {
+    parser: P,
+    rest: &'a str,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T, P: Parser<'a, T>> Iterator for FindAll<'a, T, P> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        while !self.rest.is_empty() {
+            match self.parser.parse(self.rest) {
+                Ok((value, rest)) if rest.len() < self.rest.len() => {
+                    self.rest = rest;
+                    return Some(value);
+                }
+                _ => {
+                    let skip = self.rest.chars().next().map_or(1, char::len_utf8);
+                    self.rest = &self.rest[skip..];
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum Op {
+        Add(i64),
+        Jump(i64),
+        Nop,
+    }
+
+    fn op<'a>() -> impl Parser<'a, Op> {
+        alt((
+            preceded(literal("add "), int()).map(Op::Add),
+            preceded(literal("jmp "), int()).map(Op::Jump),
+            literal("nop").map(|_| Op::Nop),
+        ))
+    }
+
+    #[test]
+    fn test_literal_and_numbers() {
+        assert_eq!(literal("ab").parse("abc"), Ok(("ab", "c")));
+        assert!(literal("ab").parse("ac").is_err());
+        assert_eq!(uint::<u8>().parse("255!"), Ok((255, "!")));
+        assert_eq!(int::<i32>().parse("-12x"), Ok((-12, "x")));
+        assert_eq!(int::<i32>().parse("+7"), Ok((7, "")));
+        assert!(uint::<u32>().parse("-1").is_err());
+        assert_eq!(
+            uint::<u8>().parse("256").unwrap_err().kind(),
+            &ParseErrorKind::Overflow { ty: "u8" }
+        );
+    }
+
+    #[test]
+    fn test_program() {
+        let program = sep_by(op(), literal("\n"));
+        assert_eq!(
+            parse_all(&program, "add +3\nnop\njmp -2"),
+            Ok(vec![Op::Add(3), Op::Nop, Op::Jump(-2)])
+        );
+    }
+
+    #[test]
+    fn test_errors_point_at_the_problem() {
+        let program = sep_by(op(), literal("\n")).skip(many(literal("\n")));
+
+        let error = parse_all(&program, "nop\nadd 1\njmp x\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.to_string(), "line 3, column 1: expected end of input");
+
+        // Within one alternative, the furthest failure wins
+        let error = parse_all(&op(), "jmp x").unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 5: expected a number");
+        // ...and on a tie the first one does
+        let error = parse_all(&op(), "mov 1").unwrap_err();
+        assert_eq!(
+            error.kind,
+            ParseErrorKind::ExpectedLiteral { literal: "add " }
+        );
+        assert_eq!(error.to_string(), "line 1, column 1: expected 'add '");
+    }
+
+    #[test]
+    fn test_many_and_spaces() {
+        let words = many(preceded(spaces(), uint::<u32>()));
+        assert_eq!(words.parse("1  2\t3;"), Ok((vec![1, 2, 3], ";")));
+        assert_eq!(words.parse(";"), Ok((vec![], ";")));
+
+        // A parser that matches nothing doesn't loop forever
+        assert_eq!(many(spaces()).parse("x"), Ok((vec![""], "x")));
+    }
+
+    #[test]
+    fn test_sep_by_leaves_trailing_separator() {
+        let list = sep_by(uint::<u32>(), literal(","));
+        assert_eq!(list.parse("1,2,"), Ok((vec![1, 2], ",")));
+        assert_eq!(list.parse(""), Ok((vec![], "")));
+    }
+
+    #[test]
+    fn test_find_all_skips_noise() {
+        let found: Vec<Op> = find_all(op(), "??nop..jmp -1 add +5 addx nop").collect();
+        assert_eq!(found, [Op::Nop, Op::Jump(-1), Op::Add(5), Op::Nop]);
+        assert_eq!(find_all(literal("é"), "aéé").count(), 2);
+    }
+}
@@ -0,0 +1,599 @@
+// Shortest-path searches over an implicit graph
+//
+// The graph is described by a successor closure, so nodes can be anything
+// hashable: a `Position`, a `(Position, Direction)` state, a whole puzzle
+// configuration. Every search records all equal-cost predecessors, which
+// makes "how many shortest paths" and "which cells are on any shortest path"
+// queries free afterwards.
+
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use crate::grid::{Grid, Position};
+
+/// Edge weights usable by `dijkstra` and `astar`
+pub trait Cost: Copy + Ord + Add<Output = Self> {
+    const ZERO: Self;
+}
+
+macro_rules! impl_cost {
+    ($($t:ty),*) => {
+        $(impl Cost for $t {
+            const ZERO: Self = 0;
+        })*
+    };
+}
+
+impl_cost!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+/// Distances and shortest-path predecessors found by a search
+#[derive(Debug, Clone)]
+pub struct Search<N, C> {
+    start: N,
+    goal: Option<N>,
+    distances: HashMap<N, C>,
+    parents: HashMap<N, Vec<N>>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Search<N, C> {
+    fn new(start: N, zero: C) -> Self {
+        Self {
+            distances: HashMap::from([(start.clone(), zero)]),
+            start,
+            goal: None,
+            parents: HashMap::new(),
+        }
+    }
+
+    pub fn start(&self) -> &N {
+        &self.start
+    }
+
+    /// The goal node that stopped the search, if one was reached
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.as_ref()
+    }
+
+    /// Cost of the shortest path to the goal
+    pub fn cost(&self) -> Option<C> {
+        self.goal.as_ref().and_then(|goal| self.distance(goal))
+    }
+
+    /// Best known distance to `node`
+    ///
+    /// Exact for every node closer than the goal; nodes further away than the
+    /// goal may only have an upper bound, since the search stops early.
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    pub fn distances(&self) -> &HashMap<N, C> {
+        &self.distances
+    }
+
+    /// One shortest path from the start to the goal, both included
+    pub fn path(&self) -> Option<Vec<N>> {
+        self.goal.as_ref().and_then(|goal| self.path_to(goal))
+    }
+
+    /// One shortest path from the start to `node`, both included
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.distances.contains_key(node) {
+            return None;
+        }
+
+        let mut path = vec![node.clone()];
+        while let Some(parent) = self.parents.get(path.last()?).and_then(|p| p.first()) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every shortest path from the start to the goal
+    pub fn all_paths(&self) -> Vec<Vec<N>> {
+        self.goal
+            .as_ref()
+            .map(|goal| self.all_paths_to(goal))
+            .unwrap_or_default()
+    }
+
+    /// Every shortest path from the start to `node`
+    ///
+    /// The number of paths can grow exponentially; use `count_paths_to` or
+    /// `nodes_on_paths_to` when only the count or the cells are needed.
+    pub fn all_paths_to(&self, node: &N) -> Vec<Vec<N>> {
+        if !self.distances.contains_key(node) {
+            return Vec::new();
+        }
+
+        let mut paths = Vec::new();
+        let mut stack = vec![vec![node.clone()]];
+        while let Some(path) = stack.pop() {
+            match self.parents.get(path.last().unwrap()) {
+                Some(parents) if !parents.is_empty() => {
+                    for parent in parents {
+                        let mut longer = path.clone();
+                        longer.push(parent.clone());
+                        stack.push(longer);
+                    }
+                }
+                _ => {
+                    let mut path = path;
+                    path.reverse();
+                    paths.push(path);
+                }
+            }
+        }
+        paths
+    }
+
+    /// Number of distinct shortest paths from the start to `node`
+    pub fn count_paths_to(&self, node: &N) -> u64 {
+        fn count<N: Eq + Hash + Clone>(
+            node: &N,
+            parents: &HashMap<N, Vec<N>>,
+            memo: &mut HashMap<N, u64>,
+        ) -> u64 {
+            if let Some(&n) = memo.get(node) {
+                return n;
+            }
+            let n = match parents.get(node) {
+                Some(ps) if !ps.is_empty() => ps.iter().map(|p| count(p, parents, memo)).sum(),
+                _ => 1,
+            };
+            memo.insert(node.clone(), n);
+            n
+        }
+
+        if !self.distances.contains_key(node) {
+            return 0;
+        }
+        count(node, &self.parents, &mut HashMap::new())
+    }
+
+    /// Every node that lies on some shortest path from the start to `node`
+    pub fn nodes_on_paths_to(&self, node: &N) -> HashSet<N> {
+        let mut seen = HashSet::new();
+        if !self.distances.contains_key(node) {
+            return seen;
+        }
+
+        let mut stack = vec![node.clone()];
+        while let Some(node) = stack.pop() {
+            if let Some(parents) = self.parents.get(&node) {
+                stack.extend(parents.iter().filter(|p| !seen.contains(*p)).cloned());
+            }
+            seen.insert(node);
+        }
+        seen
+    }
+
+    /// Record that `node` is reached through `parent` at `cost`
+    ///
+    /// Returns whether `node` needs to be (re)queued.
+    fn relax(&mut self, parent: &N, node: N, cost: C) -> bool
+    where
+        C: Ord,
+    {
+        if node == self.start {
+            return false;
+        }
+
+        match self.distances.entry(node.clone()) {
+            Entry::Vacant(entry) => {
+                entry.insert(cost);
+                self.parents.insert(node, vec![parent.clone()]);
+                true
+            }
+            Entry::Occupied(mut entry) => match cost.cmp(entry.get()) {
+                Ordering::Less => {
+                    entry.insert(cost);
+                    self.parents.insert(node, vec![parent.clone()]);
+                    true
+                }
+                Ordering::Equal => {
+                    let parents = self.parents.entry(node).or_default();
+                    if !parents.contains(parent) {
+                        parents.push(parent.clone());
+                    }
+                    false
+                }
+                Ordering::Greater => false,
+            },
+        }
+    }
+}
+
+/// Breadth-first search on an unweighted graph
+///
+/// Stops at the first node for which `is_goal` returns true; pass `|_| false`
+/// to explore everything reachable from `start`.
+pub fn bfs<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search::new(start.clone(), 0);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+
+        let next_cost = search.distances[&node] + 1;
+        for next in successors(&node) {
+            if search.relax(&node, next.clone(), next_cost) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    search
+}
+
+/// Dijkstra's algorithm on a graph with non-negative edge costs
+///
+/// `successors` yields `(neighbour, edge cost)` pairs. Stops once the first
+/// goal is settled, after every other path of the same cost has been seen.
+/// Zero-cost edges are fine for distances, but a zero-cost cycle makes the
+/// all-paths queries loop forever.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, |_| C::ZERO, is_goal)
+}
+
+/// A* search guided by `heuristic`, an estimate of the remaining cost
+///
+/// The heuristic must never overestimate, and should be consistent
+/// (`h(a) <= cost(a, b) + h(b)`), for the result to be a shortest path.
+pub fn astar<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search::new(start.clone(), C::ZERO);
+    let mut heap = BinaryHeap::from([Queued {
+        estimate: heuristic(&start),
+        cost: C::ZERO,
+        node: start,
+    }]);
+    let mut settled = HashSet::new();
+    let mut goal_cost = None;
+
+    while let Some(Queued {
+        estimate,
+        cost,
+        node,
+    }) = heap.pop()
+    {
+        // Keep going until nothing as cheap as the goal is left, so every
+        // equal-cost predecessor of the goal gets recorded
+        if goal_cost.is_some_and(|goal_cost| estimate > goal_cost) {
+            break;
+        }
+        if cost > search.distances[&node] || !settled.insert(node.clone()) {
+            continue;
+        }
+        if goal_cost.is_none() && is_goal(&node) {
+            goal_cost = Some(cost);
+            search.goal = Some(node.clone());
+        }
+
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            if search.relax(&node, next.clone(), next_cost) {
+                heap.push(Queued {
+                    estimate: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+    }
+
+    search
+}
+
+/// Heap entry ordered so that the lowest estimate pops first
+struct Queued<N, C> {
+    estimate: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed for a min-heap; among equal estimates prefer the deeper node
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+/// The 4-neighbours of `pos` inside `grid` whose cells pass `passable`
+pub fn grid_successors<'a, T>(
+    grid: &'a Grid<T>,
+    pos: Position,
+    passable: &'a impl Fn(&T) -> bool,
+) -> impl Iterator<Item = Position> + 'a {
+    pos.neighbors4()
+        .filter(move |&next| grid.get(next).is_some_and(passable))
+}
+
+/// BFS between two cells, moving orthogonally through passable cells
+pub fn grid_bfs<T>(
+    grid: &Grid<T>,
+    start: Position,
+    goal: Position,
+    passable: impl Fn(&T) -> bool,
+) -> Search<Position, usize> {
+    bfs(
+        start,
+        |pos| grid_successors(grid, *pos, &passable),
+        |&pos| pos == goal,
+    )
+}
+
+/// Dijkstra between two cells, where `cost` is the price of entering a cell
+/// and `None` marks a wall
+pub fn grid_dijkstra<T>(
+    grid: &Grid<T>,
+    start: Position,
+    goal: Position,
+    cost: impl Fn(&T) -> Option<u64>,
+) -> Search<Position, u64> {
+    dijkstra(
+        start,
+        |pos| grid_weighted_successors(grid, *pos, &cost),
+        |&pos| pos == goal,
+    )
+}
+
+/// A* between two cells with the Manhattan distance as heuristic
+///
+/// Like `grid_dijkstra`, but every cell must cost at least 1 to enter for the
+/// heuristic to be admissible.
+pub fn grid_astar<T>(
+    grid: &Grid<T>,
+    start: Position,
+    goal: Position,
+    cost: impl Fn(&T) -> Option<u64>,
+) -> Search<Position, u64> {
+    astar(
+        start,
+        |pos| grid_weighted_successors(grid, *pos, &cost),
+        |pos| pos.manhattan(goal) as u64,
+        |&pos| pos == goal,
+    )
+}
+
+fn grid_weighted_successors<'a, T>(
+    grid: &'a Grid<T>,
+    pos: Position,
+    cost: &'a impl Fn(&T) -> Option<u64>,
+) -> impl Iterator<Item = (Position, u64)> + 'a {
+    pos.neighbors4()
+        .filter_map(move |next| Some((next, grid.get(next).and_then(cost)?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Direction;
+
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#E";
+
+    fn maze() -> (Grid<char>, Position, Position) {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let start = grid.find(&'S').unwrap();
+        let end = grid.find(&'E').unwrap();
+        (grid, start, end)
+    }
+
+    #[test]
+    fn test_grid_bfs() {
+        let (grid, start, end) = maze();
+        let search = grid_bfs(&grid, start, end, |&c| c != '#');
+
+        assert_eq!(search.cost(), Some(15));
+        let path = search.path().unwrap();
+        assert_eq!(path.len(), 16);
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&end));
+        assert!(path.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+    }
+
+    #[test]
+    fn test_unreachable_goal() {
+        let grid: Grid<char> = "S#E".parse().unwrap();
+        let search = grid_bfs(&grid, Position::new(0, 0), Position::new(2, 0), |&c| {
+            c != '#'
+        });
+
+        assert_eq!(search.goal(), None);
+        assert_eq!(search.cost(), None);
+        assert_eq!(search.path(), None);
+        assert!(search.all_paths().is_empty());
+    }
+
+    #[test]
+    fn test_all_shortest_paths() {
+        // Two routes of equal length around the middle wall
+        let grid: Grid<char> = "...\n.#.\n...".parse().unwrap();
+        let (start, end) = (Position::new(0, 0), Position::new(2, 2));
+        let search = grid_bfs(&grid, start, end, |&c| c != '#');
+
+        assert_eq!(search.cost(), Some(4));
+        assert_eq!(search.all_paths().len(), 2);
+        assert_eq!(search.count_paths_to(&end), 2);
+        assert_eq!(search.nodes_on_paths_to(&end).len(), 8);
+    }
+
+    #[test]
+    fn test_bfs_explores_everything_without_goal() {
+        let search = bfs(
+            0u32,
+            |&n| [n * 2, n + 3].into_iter().filter(|&m| m < 20),
+            |_| false,
+        );
+
+        assert_eq!(search.goal(), None);
+        assert_eq!(search.distance(&0), Some(0));
+        assert_eq!(search.distance(&9), Some(3)); // 0 -> 3 -> 6 -> 9
+        assert_eq!(search.path_to(&12).unwrap(), [0, 3, 6, 12]);
+        assert_eq!(search.distance(&1), None);
+    }
+
+    #[test]
+    fn test_dijkstra_with_turn_costs() {
+        // Reindeer-maze style: moving costs 1, turning costs 1000
+        let (grid, start, end) = maze();
+        let search = dijkstra(
+            (start, Direction::EAST),
+            |&(pos, dir)| {
+                let mut moves = vec![
+                    ((pos, dir.turn_left()), 1000),
+                    ((pos, dir.turn_right()), 1000),
+                ];
+                let ahead = pos.step(dir);
+                if grid.get(ahead).is_some_and(|&c| c != '#') {
+                    moves.push(((ahead, dir), 1));
+                }
+                moves
+            },
+            |&(pos, _)| pos == end,
+        );
+
+        let path = search.path().unwrap();
+        let turns = path.windows(2).filter(|w| w[0].1 != w[1].1).count() as u64;
+        let steps = path.len() as u64 - 1 - turns;
+        assert_eq!(search.cost(), Some(steps + turns * 1000));
+        assert_eq!(path.last().unwrap().0, end);
+    }
+
+    #[test]
+    fn test_grid_weighted() {
+        let grid = Grid::from_str_with("19111\n11191", |c| c.to_digit(10).unwrap() as u64).unwrap();
+        let (start, end) = (Position::new(0, 0), Position::new(4, 1));
+
+        let dijkstra = grid_dijkstra(&grid, start, end, |&c| Some(c));
+        let astar = grid_astar(&grid, start, end, |&c| Some(c));
+        assert_eq!(dijkstra.cost(), Some(7));
+        assert_eq!(astar.cost(), Some(7));
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Random grids of cell costs where 0 is a wall
+    fn cost_grid() -> impl Strategy<Value = Grid<u64>> {
+        (2usize..9, 2usize..9).prop_flat_map(|(width, height)| {
+            prop::collection::vec(prop_oneof![1 => Just(0u64), 3 => 1u64..10], width * height)
+                .prop_map(move |cells| Grid::from_vec(width, height, cells).unwrap())
+        })
+    }
+
+    fn corners(grid: &Grid<u64>) -> (Position, Position) {
+        let end = Position::from((grid.width() - 1, grid.height() - 1));
+        (Position::new(0, 0), end)
+    }
+
+    fn assert_valid_path(grid: &Grid<u64>, path: &[Position]) {
+        for pair in path.windows(2) {
+            assert_eq!(pair[0].manhattan(pair[1]), 1);
+            assert_ne!(grid[pair[1]], 0);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_unit_costs_agree(grid in cost_grid()) {
+            let (start, end) = corners(&grid);
+            let wall = |&c: &u64| c == 0;
+
+            let bfs = grid_bfs(&grid, start, end, |c| !wall(c));
+            let dijkstra = grid_dijkstra(&grid, start, end, |c| (!wall(c)).then_some(1));
+            let astar = grid_astar(&grid, start, end, |c| (!wall(c)).then_some(1));
+
+            let cost = bfs.cost().map(|c| c as u64);
+            prop_assert_eq!(cost, dijkstra.cost());
+            prop_assert_eq!(cost, astar.cost());
+            prop_assert_eq!(bfs.count_paths_to(&end), dijkstra.count_paths_to(&end));
+            prop_assert_eq!(bfs.count_paths_to(&end), astar.count_paths_to(&end));
+            prop_assert_eq!(bfs.nodes_on_paths_to(&end), astar.nodes_on_paths_to(&end));
+
+            if let Some(path) = bfs.path() {
+                prop_assert_eq!(path.len() as u64, cost.unwrap() + 1);
+                assert_valid_path(&grid, &path);
+            }
+        }
+
+        #[test]
+        fn test_weighted_costs_agree(grid in cost_grid()) {
+            let (start, end) = corners(&grid);
+            let cost = |&c: &u64| (c != 0).then_some(c);
+
+            let dijkstra = grid_dijkstra(&grid, start, end, cost);
+            let astar = grid_astar(&grid, start, end, cost);
+
+            prop_assert_eq!(dijkstra.cost(), astar.cost());
+            prop_assert_eq!(dijkstra.count_paths_to(&end), astar.count_paths_to(&end));
+            prop_assert_eq!(dijkstra.nodes_on_paths_to(&end), astar.nodes_on_paths_to(&end));
+
+            for path in astar.all_paths() {
+                assert_valid_path(&grid, &path);
+                let total: u64 = path[1..].iter().map(|&p| grid[p]).sum();
+                prop_assert_eq!(Some(total), astar.cost());
+            }
+        }
+    }
+}
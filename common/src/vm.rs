@@ -0,0 +1,505 @@
+// A small register machine for assembly-style puzzles
+//
+// A puzzle defines its opcodes as a type implementing `Instruction`, whose
+// `execute` reads and writes the `Cpu` (registers, program counter, output)
+// and says where to go next. `Machine` owns the program and does the rest:
+// stepping, running until halt, breakpoints, a trace of recent steps and
+// detecting programs that would never halt.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::hash::Hash;
+use std::ops::{Index, IndexMut};
+
+/// Where execution goes after an instruction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    /// The following instruction
+    Next,
+    /// Relative to the current instruction: `Jump(1)` is `Next`
+    Jump(i64),
+    /// An absolute instruction index
+    Goto(usize),
+    /// Stop the machine
+    Halt,
+}
+
+/// One opcode of a puzzle's machine, with its operands
+pub trait Instruction: Clone + fmt::Debug {
+    /// What registers and output hold
+    type Value: Copy + Default + Eq + Hash + fmt::Debug;
+
+    fn execute(&self, cpu: &mut Cpu<Self::Value>) -> Control;
+}
+
+/// Fixed number of registers, all starting at `V::default()`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Registers<V> {
+    values: Vec<V>,
+}
+
+impl<V: Copy + Default> Registers<V> {
+    pub fn new(count: usize) -> Self {
+        Self {
+            values: vec![V::default(); count],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[V] {
+        &self.values
+    }
+
+    /// Reset every register to the default value
+    pub fn reset(&mut self) {
+        self.values.fill(V::default());
+    }
+}
+
+impl<V> Index<usize> for Registers<V> {
+    type Output = V;
+
+    fn index(&self, register: usize) -> &V {
+        &self.values[register]
+    }
+}
+
+impl<V> IndexMut<usize> for Registers<V> {
+    fn index_mut(&mut self, register: usize) -> &mut V {
+        &mut self.values[register]
+    }
+}
+
+/// The state an instruction can see and change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cpu<V> {
+    pub registers: Registers<V>,
+    /// Values the program has emitted, in order
+    pub output: Vec<V>,
+    pc: usize,
+}
+
+impl<V> Cpu<V> {
+    /// Index of the instruction being executed
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+}
+
+/// Why `run` returned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Halt {
+    /// An instruction returned `Control::Halt`
+    Halted,
+    /// The program counter left the program (`pc` may be negative)
+    OutOfBounds { pc: i64 },
+    /// About to execute an instruction with a breakpoint
+    Breakpoint { pc: usize },
+    /// The machine would run forever: it is back in a state first seen at
+    /// step `first_seen`
+    Loop { pc: usize, first_seen: u64 },
+    /// `run_for` used up its steps
+    StepLimit,
+}
+
+/// What counts as "back where we were" for loop detection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoopDetection {
+    Off,
+    /// Any instruction about to run a second time, for machines whose
+    /// jumps don't depend on register values
+    Pc,
+    /// The program counter and all registers repeating together, which is
+    /// exact for any deterministic machine
+    State,
+}
+
+/// One executed instruction in the trace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry<I: Instruction> {
+    /// Steps executed before this one
+    pub step: u64,
+    pub pc: usize,
+    pub instruction: I,
+    /// Registers after the instruction ran
+    pub registers: Vec<I::Value>,
+}
+
+impl<I: Instruction> fmt::Display for TraceEntry<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>8} {:>5}  {:<24} {:?}",
+            self.step,
+            self.pc,
+            format!("{:?}", self.instruction),
+            self.registers
+        )
+    }
+}
+
+/// A program loaded into a machine
+///
+/// ```
+/// use aoc_common::vm::{Control, Cpu, Halt, Instruction, Machine};
+///
+/// #[derive(Debug, Clone)]
+/// enum Op { Add(i64), Out, Jnz(i64) }
+///
+/// impl Instruction for Op {
+///     type Value = i64;
+///
+///     fn execute(&self, cpu: &mut Cpu<i64>) -> Control {
+///         match *self {
+///             Op::Add(n) => cpu.registers[0] += n,
+///             Op::Out => cpu.output.push(cpu.registers[0]),
+///             Op::Jnz(offset) if cpu.registers[0] != 0 => return Control::Jump(offset),
+///             Op::Jnz(_) => {}
+///         }
+///         Control::Next
+///     }
+/// }
+///
+/// // Count down from 3, printing each value
+/// let mut machine = Machine::new(vec![Op::Add(3), Op::Out, Op::Add(-1), Op::Jnz(-2)], 1);
+/// assert_eq!(machine.run(), Halt::OutOfBounds { pc: 4 });
+/// assert_eq!(machine.output(), [3, 2, 1]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Machine<I: Instruction> {
+    program: Vec<I>,
+    cpu: Cpu<I::Value>,
+    steps: u64,
+    breakpoints: HashSet<usize>,
+    /// Set after stopping at a breakpoint so the next run gets past it
+    resuming: bool,
+    loop_detection: LoopDetection,
+    seen_pcs: HashMap<usize, u64>,
+    seen_states: HashMap<(usize, Registers<I::Value>), u64>,
+    trace: Option<VecDeque<TraceEntry<I>>>,
+    trace_limit: usize,
+}
+
+impl<I: Instruction> Machine<I> {
+    /// Load `program` with `registers` registers, all at the default value
+    pub fn new(program: Vec<I>, registers: usize) -> Self {
+        Self {
+            program,
+            cpu: Cpu {
+                registers: Registers::new(registers),
+                output: Vec::new(),
+                pc: 0,
+            },
+            steps: 0,
+            breakpoints: HashSet::new(),
+            resuming: false,
+            loop_detection: LoopDetection::Off,
+            seen_pcs: HashMap::new(),
+            seen_states: HashMap::new(),
+            trace: None,
+            trace_limit: 0,
+        }
+    }
+
+    pub fn program(&self) -> &[I] {
+        &self.program
+    }
+
+    /// Change the program, e.g. to patch one instruction and rerun
+    pub fn program_mut(&mut self) -> &mut Vec<I> {
+        &mut self.program
+    }
+
+    pub fn cpu(&self) -> &Cpu<I::Value> {
+        &self.cpu
+    }
+
+    pub fn registers(&self) -> &Registers<I::Value> {
+        &self.cpu.registers
+    }
+
+    pub fn registers_mut(&mut self) -> &mut Registers<I::Value> {
+        &mut self.cpu.registers
+    }
+
+    pub fn output(&self) -> &[I::Value] {
+        &self.cpu.output
+    }
+
+    pub fn pc(&self) -> usize {
+        self.cpu.pc
+    }
+
+    /// Instructions executed since the last reset
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Back to the first instruction with cleared registers, output, trace
+    /// and loop history; the program, breakpoints and settings are kept
+    pub fn reset(&mut self) {
+        self.cpu.registers.reset();
+        self.cpu.output.clear();
+        self.cpu.pc = 0;
+        self.steps = 0;
+        self.resuming = false;
+        self.seen_pcs.clear();
+        self.seen_states.clear();
+        if let Some(trace) = &mut self.trace {
+            trace.clear();
+        }
+    }
+
+    /// Stop before executing the instruction at `pc`
+    pub fn add_breakpoint(&mut self, pc: usize) {
+        self.breakpoints.insert(pc);
+    }
+
+    pub fn remove_breakpoint(&mut self, pc: usize) {
+        self.breakpoints.remove(&pc);
+    }
+
+    pub fn set_loop_detection(&mut self, detection: LoopDetection) {
+        self.loop_detection = detection;
+    }
+
+    /// Keep the last `limit` executed instructions for `trace`
+    pub fn enable_trace(&mut self, limit: usize) {
+        self.trace = Some(VecDeque::with_capacity(limit.min(1024)));
+        self.trace_limit = limit;
+    }
+
+    /// The most recent executed instructions, oldest first
+    pub fn trace(&self) -> impl Iterator<Item = &TraceEntry<I>> {
+        self.trace.iter().flatten()
+    }
+
+    /// Execute one instruction, or say why the machine can't
+    pub fn step(&mut self) -> Result<(), Halt> {
+        let pc = self.cpu.pc;
+        let Some(instruction) = self.program.get(pc) else {
+            return Err(Halt::OutOfBounds { pc: pc as i64 });
+        };
+
+        if self.breakpoints.contains(&pc) && !std::mem::take(&mut self.resuming) {
+            self.resuming = true;
+            return Err(Halt::Breakpoint { pc });
+        }
+        self.resuming = false;
+
+        let first_seen = match self.loop_detection {
+            LoopDetection::Off => None,
+            LoopDetection::Pc => self.seen_pcs.insert(pc, self.steps),
+            LoopDetection::State => self
+                .seen_states
+                .insert((pc, self.cpu.registers.clone()), self.steps),
+        };
+        if let Some(first_seen) = first_seen {
+            return Err(Halt::Loop { pc, first_seen });
+        }
+
+        let control = instruction.execute(&mut self.cpu);
+        if let Some(trace) = &mut self.trace {
+            if trace.len() == self.trace_limit {
+                trace.pop_front();
+            }
+            if self.trace_limit > 0 {
+                trace.push_back(TraceEntry {
+                    step: self.steps,
+                    pc,
+                    instruction: instruction.clone(),
+                    registers: self.cpu.registers.as_slice().to_vec(),
+                });
+            }
+        }
+        self.steps += 1;
+
+        let next = match control {
+            Control::Next => pc as i64 + 1,
+            Control::Jump(offset) => pc as i64 + offset,
+            Control::Goto(target) => target as i64,
+            Control::Halt => return Err(Halt::Halted),
+        };
+        if next < 0 {
+            return Err(Halt::OutOfBounds { pc: next });
+        }
+        self.cpu.pc = next as usize;
+        Ok(())
+    }
+
+    /// Run until the machine halts, hits a breakpoint or loops
+    pub fn run(&mut self) -> Halt {
+        loop {
+            if let Err(halt) = self.step() {
+                return halt;
+            }
+        }
+    }
+
+    /// Like `run`, but give up after `max_steps` instructions
+    pub fn run_for(&mut self, max_steps: u64) -> Halt {
+        for _ in 0..max_steps {
+            if let Err(halt) = self.step() {
+                return halt;
+            }
+        }
+        Halt::StepLimit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan::{Parser, alt, int, literal, parse_all, preceded, sep_by};
+
+    /// The handheld console: accumulator in register 0
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    enum Console {
+        Acc(i64),
+        Jmp(i64),
+        Nop(i64),
+    }
+
+    impl Instruction for Console {
+        type Value = i64;
+
+        fn execute(&self, cpu: &mut Cpu<i64>) -> Control {
+            match *self {
+                Console::Acc(n) => cpu.registers[0] += n,
+                Console::Jmp(offset) => return Control::Jump(offset),
+                Console::Nop(_) => {}
+            }
+            Control::Next
+        }
+    }
+
+    const BOOT_CODE: &str =
+        "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6";
+
+    fn boot_code() -> Vec<Console> {
+        let op = alt((
+            preceded(literal("acc "), int()).map(Console::Acc),
+            preceded(literal("jmp "), int()).map(Console::Jmp),
+            preceded(literal("nop "), int()).map(Console::Nop),
+        ));
+        parse_all(&sep_by(op, literal("\n")), BOOT_CODE).unwrap()
+    }
+
+    #[test]
+    fn test_pc_loop_detection() {
+        let mut machine = Machine::new(boot_code(), 1);
+        machine.set_loop_detection(LoopDetection::Pc);
+        assert_eq!(
+            machine.run(),
+            Halt::Loop {
+                pc: 1,
+                first_seen: 1
+            }
+        );
+        assert_eq!(machine.registers()[0], 5);
+    }
+
+    #[test]
+    fn test_patching_the_program() {
+        let mut machine = Machine::new(boot_code(), 1);
+        machine.set_loop_detection(LoopDetection::Pc);
+        machine.program_mut()[7] = Console::Nop(-4);
+        assert_eq!(machine.run(), Halt::OutOfBounds { pc: 9 });
+        assert_eq!(machine.registers()[0], 8);
+
+        machine.reset();
+        assert_eq!((machine.pc(), machine.steps()), (0, 0));
+        assert_eq!(machine.run(), Halt::OutOfBounds { pc: 9 });
+    }
+
+    #[test]
+    fn test_breakpoints_and_trace() {
+        let mut machine = Machine::new(boot_code(), 1);
+        machine.add_breakpoint(4);
+        machine.enable_trace(2);
+
+        assert_eq!(machine.run(), Halt::Breakpoint { pc: 4 });
+        assert_eq!(machine.steps(), 6);
+        let trace: Vec<(usize, Console)> = machine
+            .trace()
+            .map(|entry| (entry.pc, entry.instruction.clone()))
+            .collect();
+        assert_eq!(trace, [(7, Console::Jmp(-4)), (3, Console::Acc(3))]);
+        assert_eq!(
+            machine.trace().last().unwrap().to_string(),
+            "       5     3  Acc(3)                   [5]"
+        );
+
+        // Resuming runs the instruction under the breakpoint
+        machine.remove_breakpoint(4);
+        machine.add_breakpoint(1);
+        assert_eq!(machine.run(), Halt::Breakpoint { pc: 1 });
+        assert_eq!(machine.run_for(3), Halt::StepLimit);
+    }
+
+    /// Registers a-d with copy, increment, decrement and jump-if-not-zero
+    #[derive(Debug, Clone)]
+    enum Assembunny {
+        CpyValue(i64, usize),
+        CpyRegister(usize, usize),
+        Inc(usize),
+        Dec(usize),
+        Jnz(usize, i64),
+    }
+
+    impl Instruction for Assembunny {
+        type Value = i64;
+
+        fn execute(&self, cpu: &mut Cpu<i64>) -> Control {
+            match *self {
+                Assembunny::CpyValue(value, to) => cpu.registers[to] = value,
+                Assembunny::CpyRegister(from, to) => cpu.registers[to] = cpu.registers[from],
+                Assembunny::Inc(r) => cpu.registers[r] += 1,
+                Assembunny::Dec(r) => cpu.registers[r] -= 1,
+                Assembunny::Jnz(r, offset) if cpu.registers[r] != 0 => {
+                    return Control::Jump(offset);
+                }
+                Assembunny::Jnz(..) => {}
+            }
+            Control::Next
+        }
+    }
+
+    #[test]
+    fn test_register_machine() {
+        use Assembunny::*;
+
+        // a = 41, then a += 2, a -= 1 and skip the final decrement
+        let program = vec![CpyValue(41, 0), Inc(0), Inc(0), Dec(0), Jnz(0, 2), Dec(0)];
+        let mut machine = Machine::new(program, 4);
+        assert_eq!(machine.run(), Halt::OutOfBounds { pc: 6 });
+        assert_eq!(machine.registers()[0], 42);
+
+        // b counts c down from 3 to 0 and then loops on the same state
+        let program = vec![
+            CpyValue(3, 2),
+            Dec(2),
+            Jnz(2, -1),
+            CpyRegister(2, 1),
+            Jnz(3, 0),
+        ];
+        let mut machine = Machine::new(program, 4);
+        machine.set_loop_detection(LoopDetection::State);
+        machine.registers_mut()[3] = 1;
+        assert_eq!(
+            machine.run(),
+            Halt::Loop {
+                pc: 4,
+                first_seen: 8
+            }
+        );
+        assert_eq!(machine.registers().as_slice(), [0, 0, 0, 1]);
+    }
+}
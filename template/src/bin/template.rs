@@ -1,20 +1,4 @@
-use std::time::Instant;
-
-fn main() {
-    let input = include_str!("../../data/inputs/XX.txt");
-
-    let start = Instant::now();
-    let part1_result = part1(input);
-    let part1_time = start.elapsed();
-    println!("Part 1: {} ({:?})", part1_result, part1_time);
-
-    let start = Instant::now();
-    let part2_result = part2(input);
-    let part2_time = start.elapsed();
-    println!("Part 2: {} ({:?})", part2_result, part2_time);
-
-    println!("\nTotal time: {:?}", part1_time + part2_time);
-}
+aoc_common::aoc_main!("XX");
 
 fn parse_input(input: &str) -> Vec<i64> {
     let _ = input; // TODO: implement
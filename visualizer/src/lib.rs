@@ -107,7 +107,7 @@ pub mod grid {
 
         /// Begin a new frame - returns painter ready for drawing
         /// Call this at the start of your rendering, then use draw_* methods
-        pub fn begin_frame<'a>(
+        pub fn begin_frame(
             &mut self,
             ui: &mut egui::Ui,
             bg_color: egui::Color32,